@@ -0,0 +1,58 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+/// Generate a test for each example input under examples/, so that
+/// examples/2021/day12-1.txt with an expected answer in
+/// examples/2021/day12-1.part1 becomes the test y2021_day12_1_part1.
+fn main() {
+    println!("cargo:rerun-if-changed=examples");
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let examples_dir = Path::new(&manifest_dir).join("examples");
+    let mut tests = String::new();
+    if examples_dir.is_dir() {
+        for year_dir in sorted_entries(&examples_dir) {
+            if !year_dir.is_dir() {
+                continue;
+            }
+            let year = file_name(&year_dir);
+            for input in sorted_entries(&year_dir) {
+                if input.extension() != Some(OsStr::new("txt")) {
+                    continue;
+                }
+                let name = input.file_stem().unwrap().to_str().unwrap().to_string();
+                let day = name
+                    .strip_prefix("day")
+                    .and_then(|rest| rest.split('-').next())
+                    .unwrap_or_else(|| panic!("Example {} is not named dayN-K.txt.", input.display()));
+                for part in 1..=2 {
+                    let expected = input.with_extension(format!("part{}", part));
+                    if expected.exists() {
+                        tests += &format!(
+                            "#[test]\nfn y{}_{}_part{}() {{\n    check_example({}, {}, {}, {:?}, {:?});\n}}\n\n",
+                            year, name.replace('-', "_"), part,
+                            year, day, part,
+                            input.to_str().unwrap(), expected.to_str().unwrap()
+                        );
+                    }
+                }
+            }
+        }
+    }
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("example_tests.rs");
+    fs::write(out_path, tests).expect("Could not write generated example tests.");
+}
+
+fn sorted_entries(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .expect("Could not read examples directory.")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().unwrap().to_str().unwrap().to_string()
+}
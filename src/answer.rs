@@ -0,0 +1,61 @@
+use std::fmt;
+
+use json::JsonValue;
+
+/// The answer to one part of a puzzle. Most answers are
+/// numbers, but some are text, such as letters drawn out
+/// in a grid of pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String)
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::UInt(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s)
+        }
+    }
+}
+
+/// Numeric answers become JSON numbers, and text answers strings.
+impl From<&Answer> for JsonValue {
+    fn from(answer: &Answer) -> JsonValue {
+        match answer {
+            Answer::Int(n) => JsonValue::from(*n),
+            Answer::UInt(n) => JsonValue::from(*n),
+            Answer::Text(s) => JsonValue::from(s.as_str())
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($variant:ident, $target:ty, [$($t:ty),*]) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(Int, i64, [i8, i16, i32, i64, isize]);
+answer_from_int!(UInt, u64, [u8, u16, u32, u64, usize]);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
@@ -0,0 +1,58 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::Error;
+
+thread_local! {
+    /// The token for the work being done on this thread, if any.
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// A flag shared between the thread running a task and the one
+/// watching it, which is set when the task is given up on.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Make this the token that check looks at on the current thread.
+    pub fn install(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+}
+
+/// Fail with a timeout error if the work on this thread has been
+/// cancelled. Long searches call this as they go, so that they stop
+/// soon after timing out rather than running on in the background.
+pub fn check() -> Result<(), Error> {
+    let cancelled = CURRENT.with(|current| current.borrow().as_ref().is_some_and(|token| token.is_cancelled()));
+    if cancelled {
+        Err(Error::timeout("Cancelled after timing out."))
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn check_fails_once_cancelled_on_this_thread_only() {
+    let token = CancelToken::new();
+    token.install();
+    assert!(check().is_ok());
+    token.cancel();
+    assert!(check().is_err());
+    assert!(std::thread::spawn(check).join().unwrap().is_ok());
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+use crate::error::Error;
+
+/// Match a line against the regex and build a value from what
+/// it captures, failing if the line doesn't match.
+pub fn parse_captures<T, F>(regex: &Regex, line: &str, f: F) -> Result<T, Error>
+where F: FnOnce(&Captures) -> Result<T, Error> {
+    let captures = regex.captures(line)
+        .ok_or_else(|| Error::parse(format!("Expected a line matching {}.", regex)))?;
+    f(&captures)
+}
+
+/// The text captured by the named group.
+pub fn named_str<'t>(captures: &Captures<'t>, name: &str) -> Result<&'t str, Error> {
+    captures.name(name)
+        .map(|m| m.as_str())
+        .ok_or_else(|| Error::parse(format!("Nothing was captured for {}.", name)))
+}
+
+/// The text captured by the group at the index, counting from 1.
+pub fn group_str<'t>(captures: &Captures<'t>, index: usize) -> Result<&'t str, Error> {
+    captures.get(index)
+        .map(|m| m.as_str())
+        .ok_or_else(|| Error::parse(format!("Nothing was captured for group {}.", index)))
+}
+
+/// Parse the text captured by the named group as a T.
+pub fn named<T: FromStr>(captures: &Captures, name: &str) -> Result<T, Error>
+where T::Err: Display {
+    parse_capture(named_str(captures, name)?, name)
+}
+
+/// Parse the text captured by the group at the index as a T.
+pub fn group<T: FromStr>(captures: &Captures, index: usize) -> Result<T, Error>
+where T::Err: Display {
+    parse_capture(group_str(captures, index)?, &format!("group {}", index))
+}
+
+fn parse_capture<T: FromStr>(text: &str, what: &str) -> Result<T, Error>
+where T::Err: Display {
+    text.parse()
+        .map_err(|e| Error::parse(format!("Could not parse {} \"{}\" ({}).", what, text, e)))
+}
+
+#[test]
+fn captures_are_parsed_by_name_and_index() {
+    let regex = Regex::new(r"rect (?P<width>\d+)x(?P<height>\w+)").unwrap();
+    let (width, height) = parse_captures(&regex, "rect 3x2", |c| {
+        Ok((named::<usize>(c, "width")?, group::<usize>(c, 2)?))
+    }).unwrap();
+    assert_eq!((width, height), (3, 2));
+
+    let e = parse_captures(&regex, "rect 3xy", |c| named::<usize>(c, "height")).unwrap_err();
+    assert_eq!(e.message, "Could not parse height \"y\" (invalid digit found in string).");
+    assert!(parse_captures(&regex, "rotate row y=0 by 4", |c| group::<usize>(c, 1)).is_err());
+}
@@ -1,9 +1,11 @@
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::hash::Hash;
-use std::cmp::{self, max, min};
+use std::cmp::{self, max, min, Reverse};
 
 use priority_queue::PriorityQueue;
 
+use crate::error::Error;
+
 #[derive(Clone, Eq, Hash, PartialEq, Debug, PartialOrd, Ord)]
 pub struct Coord {
     pub x: usize,
@@ -21,7 +23,37 @@ pub struct SignedCoord {
     pub y: isize
 }
 
-#[derive(Clone)]
+/// A rectangle of cells in a grid, xsize x ysize
+/// with its top left corner at x,y.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub xsize: usize,
+    pub ysize: usize
+}
+
+/// Offsets to the cells above, right of, below and left of a cell.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to all eight cells around a cell, including diagonals.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1)
+];
+
+/// What happens to neighbours that fall off the edge of a grid:
+/// either they are left out, or they wrap around to the other side.
+/// On a grid only one or two cells across, wrapping around can
+/// land back on the cell itself, or on the same cell twice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edges {
+    Bounded,
+    Wrap
+}
+
+#[derive(Clone, Debug)]
 pub struct Grid<T: Clone> {
     pub xsize: usize,
     pub ysize: usize,
@@ -50,6 +82,130 @@ impl<'a, T: Clone> Grid<T> {
         Grid{xsize, ysize, elements}
     }
 
+    /// Create a new grid of size xsize x ysize with
+    /// the element at each x,y given by f(x, y).
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(
+        xsize: usize,
+        ysize: usize,
+        mut f: F
+    ) -> Grid<T> {
+        let mut elements = Vec::<T>::with_capacity(xsize * ysize);
+        for y in 0..ysize {
+            for x in 0..xsize {
+                elements.push(f(x, y));
+            }
+        }
+        Grid{xsize, ysize, elements}
+    }
+
+    /// Create a grid from lines of characters, with one element
+    /// per character converted by parse_char. Fails if a character
+    /// can't be converted or the lines aren't all the same length,
+    /// pointing the error at the line.
+    pub fn from_lines<S, F>(
+        lines: &[S],
+        parse_char: F
+    ) -> Result<Grid<T>, Error>
+    where S: AsRef<str>, F: Fn(char) -> Result<T, Error> {
+        let xsize = match lines.first() {
+            Some(line) if !line.as_ref().is_empty() => line.as_ref().chars().count(),
+            _ => return Err(Error::parse("The grid is empty."))
+        };
+        let mut elements = Vec::<T>::with_capacity(xsize * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            let at_line = |e: Error| e.at_line(y + 1, line);
+            let length = line.chars().count();
+            if length != xsize {
+                return Err(at_line(Error::parse(format!(
+                    "Expected a row of {} characters, found {}.", xsize, length
+                ))));
+            }
+            for c in line.chars() {
+                elements.push(parse_char(c).map_err(at_line)?);
+            }
+        }
+        Ok(Grid{xsize, ysize: lines.len(), elements})
+    }
+
+    /// Create a grid from text with a row on each line,
+    /// as from_lines does.
+    pub fn from_str<F>(
+        text: &str,
+        parse_char: F
+    ) -> Result<Grid<T>, Error>
+    where F: Fn(char) -> Result<T, Error> {
+        Grid::from_lines(&text.lines().collect::<Vec<&str>>(), parse_char)
+    }
+
+    /// Draw the grid as lines of characters, converting
+    /// each element with render_char.
+    pub fn render<F: Fn(&T) -> char>(
+        &self,
+        render_char: F
+    ) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&render_char).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Iterate over the elements row by row,
+    /// along with the coords of each one.
+    pub fn iter_coords(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let xsize = self.xsize.max(1);
+        self.elements
+            .iter()
+            .enumerate()
+            .map(move |(i, element)| (Coord { x: i % xsize, y: i / xsize }, element))
+    }
+
+    /// Iterate over the elements row by row, allowing
+    /// each one to be changed in place.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.elements.iter_mut()
+    }
+
+    /// Iterate over the rows from top to bottom.
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.elements.chunks(self.xsize.max(1))
+    }
+
+    /// Iterate over the columns from left to right, each
+    /// one an iterator over its elements from top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.xsize).map(move |x| self.elements.iter().skip(x).step_by(self.xsize))
+    }
+
+    /// Create a grid of the same size with f
+    /// applied to each element.
+    pub fn map<U: Clone, F: Fn(&T) -> U>(
+        &self,
+        f: F
+    ) -> Grid<U> {
+        Grid::with_elements(self.xsize, self.ysize, self.elements.iter().map(f).collect())
+    }
+
+    /// Create a grid from the elements of this grid and another
+    /// of the same size, combined pairwise by f.
+    pub fn zip_with<U: Clone, V: Clone, F: Fn(&T, &U) -> V>(
+        &self,
+        other: &Grid<U>,
+        f: F
+    ) -> Grid<V> {
+        if (self.xsize, self.ysize) != (other.xsize, other.ysize) {
+            panic!(
+                "Can't zip a {}x{} grid with a {}x{} grid.",
+                self.xsize, self.ysize, other.xsize, other.ysize
+            );
+        }
+        let elements = self.elements.iter()
+            .zip(&other.elements)
+            .map(|(a, b)| f(a, b))
+            .collect();
+        Grid::with_elements(self.xsize, self.ysize, elements)
+    }
+
     /// Read the element at x,y in the grid
     pub fn get(
         &self,
@@ -85,6 +241,136 @@ impl<'a, T: Clone> Grid<T> {
         self.elements[index] = val;
     }
 
+    /// Swap rows for columns, so the element at x,y ends up at y,x.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.ysize, self.xsize, |x, y| self.get(y, x).clone())
+    }
+
+    /// Turn the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        Grid::from_fn(self.ysize, self.xsize, |x, y| self.get(y, self.ysize - 1 - x).clone())
+    }
+
+    /// Turn the grid a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        Grid::from_fn(self.ysize, self.xsize, |x, y| self.get(self.xsize - 1 - y, x).clone())
+    }
+
+    /// Mirror the grid left to right.
+    pub fn flip_h(&self) -> Grid<T> {
+        Grid::from_fn(self.xsize, self.ysize, |x, y| self.get(self.xsize - 1 - x, y).clone())
+    }
+
+    /// Mirror the grid top to bottom.
+    pub fn flip_v(&self) -> Grid<T> {
+        Grid::from_fn(self.xsize, self.ysize, |x, y| self.get(x, self.ysize - 1 - y).clone())
+    }
+
+    /// Copy out the part of the grid inside rect,
+    /// which must lie within the grid.
+    pub fn subgrid(&self, rect: Rect) -> Grid<T> {
+        self.check_bounds(rect.x + rect.xsize.max(1) - 1, rect.y + rect.ysize.max(1) - 1);
+        Grid::from_fn(rect.xsize, rect.ysize, |x, y| self.get(rect.x + x, rect.y + y).clone())
+    }
+
+    /// Overwrite part of the grid with another grid, placing
+    /// its top left corner at offset. The other grid
+    /// must fit within this one.
+    pub fn splice(&mut self, other: &Grid<T>, offset: Coord) {
+        for (Coord {x, y}, element) in other.iter_coords() {
+            self.set(offset.x + x, offset.y + y, element.clone());
+        }
+    }
+
+    /// Create a grid of nx x ny copies of this one.
+    pub fn tile(&self, nx: usize, ny: usize) -> Grid<T> {
+        Grid::from_fn(self.xsize * nx, self.ysize * ny, |x, y| {
+            self.get(x % self.xsize, y % self.ysize).clone()
+        })
+    }
+
+    /// Shift the elements of row y right by the given
+    /// amount, wrapping those that fall off the end
+    /// back round to the start.
+    pub fn rotate_row(&mut self, y: usize, by: usize) {
+        self.check_bounds(0, y);
+        let start = self.calc_index(0, y);
+        self.elements[start..start + self.xsize].rotate_right(by % self.xsize);
+    }
+
+    /// Shift the elements of column x down by the given
+    /// amount, wrapping those that fall off the bottom
+    /// back round to the top.
+    pub fn rotate_col(&mut self, x: usize, by: usize) {
+        self.check_bounds(x, 0);
+        let column: Vec<T> = (0..self.ysize).map(|y| self.get(x, y).clone()).collect();
+        for (y, element) in column.into_iter().enumerate() {
+            self.set(x, (y + by % self.ysize) % self.ysize, element);
+        }
+    }
+
+    /// The coords and values of the cells above, below,
+    /// left and right of x,y that are within the grid.
+    pub fn neighbours4(
+        &self,
+        x: usize,
+        y: usize
+    ) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbours(x, y, &ORTHOGONAL, Edges::Bounded)
+    }
+
+    /// The coords and values of the cells around x,y,
+    /// including diagonals, that are within the grid.
+    pub fn neighbours8(
+        &self,
+        x: usize,
+        y: usize
+    ) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbours(x, y, &SURROUNDING, Edges::Bounded)
+    }
+
+    /// The coords and values of the cells at each offset
+    /// in the stencil from x,y, in the stencil's order.
+    pub fn neighbours<'g>(
+        &'g self,
+        x: usize,
+        y: usize,
+        stencil: &'g [(isize, isize)],
+        edges: Edges
+    ) -> impl Iterator<Item = (Coord, &'g T)> {
+        self.neighbour_coords(x, y, stencil, edges)
+            .map(move |coord| {
+                let value = self.get(coord.x, coord.y);
+                (coord, value)
+            })
+    }
+
+    /// The coords at each offset in the stencil from x,y.
+    /// This doesn't borrow the grid, so the cells can be
+    /// changed while looping over them. An empty grid has
+    /// no neighbours, whatever the edges do.
+    pub fn neighbour_coords<'s>(
+        &self,
+        x: usize,
+        y: usize,
+        stencil: &'s [(isize, isize)],
+        edges: Edges
+    ) -> impl Iterator<Item = Coord> + 's {
+        let (xsize, ysize) = (self.xsize as isize, self.ysize as isize);
+        stencil.iter().filter_map(move |(dx, dy)| {
+            let (x, y) = (x as isize + dx, y as isize + dy);
+            match edges {
+                _ if xsize == 0 || ysize == 0 => None,
+                Edges::Bounded if x < 0 || y < 0 || x >= xsize || y >= ysize => None,
+                Edges::Bounded => Some(Coord { x: x as usize, y: y as usize }),
+                Edges::Wrap => Some(Coord {
+                    x: x.rem_euclid(xsize) as usize,
+                    y: y.rem_euclid(ysize) as usize
+                })
+            }
+        })
+    }
+
     /// Make sure that the given x, y coords are
     /// within the bounds of the grid.
     fn check_bounds(
@@ -108,6 +394,34 @@ impl<'a, T: Clone> Grid<T> {
     }
 }
 
+impl Grid<u8> {
+    /// Create a grid of the digits in lines of text.
+    pub fn from_digits<S: AsRef<str>>(lines: &[S]) -> Result<Grid<u8>, Error> {
+        Grid::from_lines(lines, |c| {
+            c.to_digit(10)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| Error::parse(format!("Expected a digit, found {}.", c)))
+        })
+    }
+}
+
+/// Grids of elements that display as single characters,
+/// such as digits, display as the lines of characters
+/// that from_lines reads.
+impl<T: Clone + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for element in row {
+                write!(f, "{}", element)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 // The lifetime specifier here makes sure that
 // the elements reference living inside the iterator
 // does not outlive the grid. This iterator returns
@@ -143,37 +457,75 @@ impl<'a, T> Iterator for GridIterator<'a, T> {
     }
 }
 
-pub struct MinPriorityQueue<T: Hash + Eq> {
-    queue: PriorityQueue<T, u32>
+/// A priority queue that pops the item with the lowest priority
+/// first. Priorities can be anything ordered, such as costs, signed
+/// numbers or tuples, and default to u32.
+pub struct MinPriorityQueue<T: Hash + Eq, P: Ord = u32> {
+    queue: PriorityQueue<T, Reverse<P>>
 }
 
-impl<T: Hash + Eq> MinPriorityQueue<T> {
-    pub fn new() -> MinPriorityQueue<T> {
+impl<T: Hash + Eq, P: Ord> MinPriorityQueue<T, P> {
+    pub fn new() -> MinPriorityQueue<T, P> {
         MinPriorityQueue {
             queue: PriorityQueue::new()
         }
     }
 
-    pub fn pop(&mut self) -> Option<(T, u32)> {
-        match self.queue.pop() {
-            None => None,
-            Some((t, p)) => Some((t, u32::MAX - p))
-        }
+    /// Remove the item with the lowest priority.
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        self.queue.pop().map(|(t, Reverse(p))| (t, p))
     }
 
-    pub fn push(&mut self, item: T, priority: u32) {
-        self.queue.push(item, u32::MAX - priority);
+    /// The item with the lowest priority, without removing it.
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.queue.peek().map(|(t, Reverse(p))| (t, p))
     }
 
-    pub fn get(&self, item: &T) -> Option<(&T, u32)> {
-        match self.queue.get(item) {
-            None => None,
-            Some((t, p)) => Some((t, u32::MAX - p))
-        }
+    /// Add the item, or change its priority if it's already queued.
+    pub fn push(&mut self, item: T, priority: P) {
+        self.queue.push(item, Reverse(priority));
+    }
+
+    /// Add the item, or raise its priority if it's already
+    /// queued with a lower one.
+    pub fn push_increase(&mut self, item: T, priority: P) {
+        self.queue.push_decrease(item, Reverse(priority));
     }
 
-    pub fn change_priority(&mut self, item: &T, new_priority: u32) {
-        self.queue.change_priority(item, u32::MAX - new_priority);
+    /// Add the item, or lower its priority if it's already
+    /// queued with a higher one, as when a cheaper way to
+    /// it is found.
+    pub fn push_decrease(&mut self, item: T, priority: P) {
+        self.queue.push_increase(item, Reverse(priority));
+    }
+
+    pub fn get(&self, item: &T) -> Option<(&T, P)>
+    where P: Clone {
+        self.queue.get(item).map(|(t, Reverse(p))| (t, p.clone()))
+    }
+
+    pub fn change_priority(&mut self, item: &T, new_priority: P) {
+        self.queue.change_priority(item, Reverse(new_priority));
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Iterate over the queued items and their
+    /// priorities, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &P)> {
+        self.queue.iter().map(|(t, Reverse(p))| (t, p))
+    }
+}
+
+impl<T: Hash + Eq, P: Ord> Default for MinPriorityQueue<T, P> {
+    fn default() -> MinPriorityQueue<T, P> {
+        MinPriorityQueue::new()
     }
 }
 
@@ -214,3 +566,97 @@ impl Display for NumRange {
         write!(f, "NumRange [{} to {}]", self.min, self.max)
     }
 }
+
+#[test]
+fn grid_from_lines_round_trips() {
+    let grid = Grid::from_digits(&["219", "398"]).unwrap();
+    assert_eq!((grid.xsize, grid.ysize, *grid.get(2, 1)), (3, 2, 8));
+    assert_eq!(grid.to_string(), "219\n398");
+
+    let lights = Grid::from_str(".#\n#.\n", |c| Ok(c == '#')).unwrap();
+    assert_eq!(lights.render(|on| if *on { '#' } else { '.' }), ".#\n#.");
+
+    let e = Grid::from_digits(&["219", "39", "876"]).unwrap_err();
+    assert_eq!(e.line, Some(2));
+    assert_eq!(Grid::from_digits(&["2x9"]).unwrap_err().line, Some(1));
+}
+
+#[test]
+fn neighbours_stay_in_bounds_or_wrap() {
+    let grid = Grid::from_digits(&["123", "456", "789"]).unwrap();
+    let values = |neighbours: Vec<(Coord, &u8)>| neighbours.into_iter().map(|(_, v)| *v).collect::<Vec<u8>>();
+    assert_eq!(values(grid.neighbours4(0, 0).collect()), vec![2, 4]);
+    assert_eq!(values(grid.neighbours8(1, 1).collect()), vec![1, 2, 3, 4, 6, 7, 8, 9]);
+    assert_eq!(values(grid.neighbours8(2, 2).collect()), vec![5, 6, 8]);
+    assert_eq!(values(grid.neighbours(0, 0, &ORTHOGONAL, Edges::Wrap).collect()), vec![7, 2, 4, 3]);
+    let knight = [(1, 2), (2, 1)];
+    let coords: Vec<Coord> = grid.neighbour_coords(0, 0, &knight, Edges::Bounded).collect();
+    assert_eq!(coords, vec![Coord { x: 1, y: 2 }, Coord { x: 2, y: 1 }]);
+
+    // Wrapping on a thin grid can give the cell itself or repeat a cell.
+    let thin = Grid::from_digits(&["12"]).unwrap();
+    assert_eq!(values(thin.neighbours(0, 0, &ORTHOGONAL, Edges::Wrap).collect()), vec![1, 2, 1, 2]);
+    let empty = Grid::with_elements(0, 0, Vec::<u8>::new());
+    assert_eq!(empty.neighbour_coords(0, 0, &SURROUNDING, Edges::Wrap).count(), 0);
+}
+
+#[test]
+fn grid_iterates_with_coords_rows_and_columns() {
+    let mut grid = Grid::from_digits(&["123", "456"]).unwrap();
+    let last = grid.iter_coords().last().map(|(coord, digit)| (coord, *digit));
+    assert_eq!(last, Some((Coord { x: 2, y: 1 }, 6)));
+    assert_eq!(grid.rows().map(|row| row.to_vec()).collect::<Vec<_>>(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    assert_eq!(grid.columns().map(|column| column.copied().collect()).collect::<Vec<Vec<u8>>>(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+    grid.iter_mut().for_each(|digit| *digit *= 2);
+    let odd = grid.map(|digit| digit % 4 != 0);
+    assert_eq!(odd.render(|odd| if *odd { '#' } else { '.' }), "#.#\n.#.");
+    assert_eq!(grid.zip_with(&odd, |digit, odd| if *odd { 0 } else { *digit }).to_string(), "040\n8012");
+}
+
+#[test]
+fn grid_transforms_move_elements() {
+    let grid = Grid::from_digits(&["123", "456"]).unwrap();
+    assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+    assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+    assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+    assert_eq!(grid.rotate_cw().rotate_ccw().to_string(), grid.to_string());
+    assert_eq!(grid.flip_h().to_string(), "321\n654");
+    assert_eq!(grid.flip_v().to_string(), "456\n123");
+    assert_eq!(grid.subgrid(Rect { x: 1, y: 0, xsize: 2, ysize: 2 }).to_string(), "23\n56");
+    assert_eq!(grid.tile(2, 2).to_string(), "123123\n456456\n123123\n456456");
+}
+
+#[test]
+fn grid_splices_and_rotates_in_place() {
+    let mut grid = Grid::new(4, 3, 0u8);
+    grid.splice(&Grid::new(2, 2, 1), Coord { x: 1, y: 1 });
+    assert_eq!(grid.to_string(), "0000\n0110\n0110");
+    grid.rotate_row(1, 3);
+    assert_eq!(grid.to_string(), "0000\n1100\n0110");
+    grid.rotate_col(0, 4);
+    assert_eq!(grid.to_string(), "0000\n0100\n1110");
+}
+
+#[test]
+fn min_priority_queue_pops_lowest_of_any_priority() {
+    let mut queue = MinPriorityQueue::<&str, (i64, u8)>::new();
+    queue.push("far", (5_000_000_000, 0));
+    queue.push("behind", (-3, 1));
+    queue.push("tied", (-3, 0));
+    queue.push_decrease("far", (7_000_000_000, 0));
+    assert_eq!(queue.get(&"far"), Some((&"far", (5_000_000_000, 0))));
+    queue.push_increase("tied", (6_000_000_000, 0));
+    assert_eq!((queue.len(), queue.peek()), (3, Some((&"behind", &(-3, 1)))));
+    assert_eq!(queue.iter().count(), 3);
+
+    let order: Vec<&str> = std::iter::from_fn(|| queue.pop().map(|(item, _)| item)).collect();
+    assert_eq!(order, vec!["behind", "far", "tied"]);
+    assert!(queue.is_empty());
+
+    // Priorities default to u32, as 2021 day 15 first used them.
+    let mut risks = MinPriorityQueue::new();
+    risks.push(Coord { x: 0, y: 0 }, u32::MAX);
+    risks.change_priority(&Coord { x: 0, y: 0 }, 0);
+    assert_eq!(risks.pop(), Some((Coord { x: 0, y: 0 }, 0)));
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+/// Exit code when an answer didn't match the expected one.
+pub const EXIT_WRONG_ANSWER: i32 = 1;
+/// Exit code when the command line arguments are invalid.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code when a puzzle input couldn't be read, parsed or solved.
+pub const EXIT_PUZZLE: i32 = 3;
+/// Exit code when a part was given up on for taking too long.
+pub const EXIT_TIMEOUT: i32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Usage,
+    Input,
+    Parse,
+    Solve,
+    Timeout
+}
+
+/// An error from the command line, or from reading, parsing or
+/// solving a puzzle. Parse errors can point at the line of the
+/// input that caused them, and the runner fills in which day
+/// was being run.
+#[derive(Clone, Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub line: Option<usize>,
+    pub text: Option<String>
+}
+
+impl Error {
+    pub fn new<S: Into<String>>(kind: ErrorKind, message: S) -> Error {
+        Error { kind, message: message.into(), year: None, day: None, line: None, text: None }
+    }
+
+    pub fn usage<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Usage, message)
+    }
+
+    pub fn input<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Input, message)
+    }
+
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Parse, message)
+    }
+
+    pub fn solve<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Solve, message)
+    }
+
+    pub fn timeout<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Timeout, message)
+    }
+
+    /// Point the error at a line of the input, numbered from 1.
+    /// Keeps the innermost line if one is already set.
+    pub fn at_line(mut self, line: usize, text: &str) -> Error {
+        if self.line.is_none() {
+            self.line = Some(line);
+            self.text = Some(text.to_string());
+        }
+        self
+    }
+
+    /// Record which day was being run when the error happened.
+    pub fn for_day(mut self, year: u16, day: u8) -> Error {
+        self.year.get_or_insert(year);
+        self.day.get_or_insert(day);
+        self
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self.kind {
+            ErrorKind::Usage => EXIT_USAGE,
+            ErrorKind::Timeout => EXIT_TIMEOUT,
+            _ => EXIT_PUZZLE
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut location = Vec::<String>::new();
+        if let (Some(year), Some(day)) = (self.year, self.day) {
+            location.push(format!("{} day {}", year, day));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {}", line));
+        }
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(text) = &self.text {
+            write!(f, "\n    {}", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Error {
+        Error::parse(format!("Invalid number ({}).", e))
+    }
+}
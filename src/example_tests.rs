@@ -0,0 +1,18 @@
+//! Tests generated by build.rs from the example inputs under
+//! examples/, checking each part's answer against the expected one.
+
+use crate::build_registry;
+use crate::input_file::{read_all_to_string, PuzzleInput};
+
+fn check_example(year: u16, day: u8, part: u8, input_file_path: &str, expected_file_path: &str) {
+    let registry = build_registry();
+    let solution = registry.get(year, day)
+        .unwrap_or_else(|| panic!("No solution to {} day {} for example.", year, day));
+    let (answer, _) = solution.run_timed(part, &PuzzleInput::file(input_file_path))
+        .unwrap_or_else(|e| panic!("{}", e));
+    let expected = read_all_to_string(&PuzzleInput::file(expected_file_path))
+        .unwrap_or_else(|e| panic!("{}", e));
+    assert_eq!(answer.to_string().trim_end(), expected.trim_end());
+}
+
+include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
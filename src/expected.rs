@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::input_file::{read_all_to_string, PuzzleInput};
+
+/// Default location of the expected answers file.
+pub const ANSWERS_FILE: &str = "answers.json";
+
+/// Known correct answers, keyed by year, day and part. Stored
+/// as JSON in the form {"2021": {"14": {"1": 3143, "2": "..."}}},
+/// where answers can be given as numbers or strings.
+pub struct ExpectedAnswers {
+    answers: HashMap<(u16, u8, u8), String>
+}
+
+impl ExpectedAnswers {
+    /// Load the expected answers from the file at path. A file
+    /// that doesn't exist is treated as having no answers.
+    pub fn load(path: &str) -> Result<ExpectedAnswers, Error> {
+        let mut answers = HashMap::<(u16, u8, u8), String>::new();
+        if !Path::new(path).exists() {
+            return Ok(ExpectedAnswers { answers });
+        }
+        let contents = read_all_to_string(&PuzzleInput::file(path))?;
+        let invalid = |what: &str| Error::input(format!("Answers file {} has an invalid {}.", path, what));
+        let json = json::parse(&contents).map_err(|_| invalid("JSON document"))?;
+        for (year, days) in json.entries() {
+            let year: u16 = year.parse().map_err(|_| invalid("year"))?;
+            for (day, parts) in days.entries() {
+                let day: u8 = day.parse().map_err(|_| invalid("day"))?;
+                for (part, answer) in parts.entries() {
+                    let part: u8 = part.parse().map_err(|_| invalid("part"))?;
+                    let answer = match answer.as_str() {
+                        Some(s) => s.to_string(),
+                        None => answer.dump()
+                    };
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+        Ok(ExpectedAnswers { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+fn answers_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("aoc-answers-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path.display().to_string()
+}
+
+#[test]
+fn load_reads_number_and_string_answers() {
+    let path = answers_file("valid", r#"{"2021": {"14": {"1": 3143, "2": "ABC\nDEF"}}, "2015": {"4": {"1": 282749}}}"#);
+    let expected = ExpectedAnswers::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(expected.get(2021, 14, 1), Some("3143"));
+    assert_eq!(expected.get(2021, 14, 2), Some("ABC\nDEF"));
+    assert_eq!(expected.get(2015, 4, 1), Some("282749"));
+    assert_eq!(expected.get(2015, 4, 2), None);
+}
+
+#[test]
+fn load_treats_a_missing_file_as_having_no_answers() {
+    let path = std::env::temp_dir().join(format!("aoc-answers-missing-{}.json", std::process::id()));
+    let expected = ExpectedAnswers::load(&path.display().to_string()).unwrap();
+    assert_eq!(expected.get(2021, 14, 1), None);
+}
+
+#[test]
+fn load_rejects_invalid_keys() {
+    use crate::error::ErrorKind;
+
+    let cases = [
+        ("year", r#"{"twenty": {"1": {"1": 1}}}"#),
+        ("day", r#"{"2021": {"first": {"1": 1}}}"#),
+        ("part", r#"{"2021": {"1": {"300": 1}}}"#),
+        ("JSON document", r#"{"2021": "#)
+    ];
+    for (what, contents) in cases.iter() {
+        let path = answers_file("invalid", contents);
+        let error = ExpectedAnswers::load(&path).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind, ErrorKind::Input);
+        assert_eq!(error.message, format!("Answers file {} has an invalid {}.", path, what));
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// How long to wait for the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The status code and body of an HTTP response.
+pub struct Response {
+    pub status: u16,
+    pub body: String
+}
+
+/// Make a plain HTTP/1.0 request to the path under the base URL,
+/// e.g. http://localhost:8080. HTTPS is not supported, as that
+/// would need a TLS implementation.
+pub fn request(
+    base_url: &str,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>
+) -> Result<Response, Error> {
+    let (host, port, prefix) = split_url(base_url)?;
+    let mut stream = TcpStream::connect((host.as_str(), port))
+        .map_err(|e| Error::input(format!("Could not connect to {} ({}).", base_url, e)))?;
+    stream.set_read_timeout(Some(TIMEOUT)).ok();
+    stream.set_write_timeout(Some(TIMEOUT)).ok();
+
+    let mut request = format!("{} {}{} HTTP/1.0\r\nHost: {}\r\n", method, prefix, path, host);
+    for (name, value) in headers {
+        request += &format!("{}: {}\r\n", name, value);
+    }
+    if let Some(body) = body {
+        request += &format!("Content-Length: {}\r\n", body.len());
+    }
+    request += "\r\n";
+    request += body.unwrap_or("");
+
+    let failed = |e: std::io::Error| Error::input(format!("Request to {} failed ({}).", base_url, e));
+    stream.write_all(request.as_bytes()).map_err(failed)?;
+    let mut response = Vec::<u8>::new();
+    stream.read_to_end(&mut response).map_err(failed)?;
+    parse_response(&String::from_utf8_lossy(&response))
+}
+
+/// Split http://host:port/prefix into its parts, with
+/// port 80 if none is given.
+fn split_url(url: &str) -> Result<(String, u16, String), Error> {
+    let rest = url.strip_prefix("http://")
+        .ok_or_else(|| Error::input(format!("Only http:// URLs are supported, not {}.", url)))?;
+    let (authority, prefix) = match rest.find('/') {
+        Some(i) => (&rest[..i], rest[i..].trim_end_matches('/')),
+        None => (rest, "")
+    };
+    let (host, port) = match authority.split_once(':') {
+        Some((host, port)) => {
+            let port = port.parse().map_err(|_| Error::input(format!("Invalid port in {}.", url)))?;
+            (host, port)
+        },
+        None => (authority, 80)
+    };
+    Ok((host.to_string(), port, prefix.to_string()))
+}
+
+fn parse_response(response: &str) -> Result<Response, Error> {
+    let invalid = || Error::input("Invalid HTTP response.");
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+    Ok(Response { status, body: body.to_string() })
+}
+
+/// A stand-in server on a free local port that reads one request,
+/// up to the end of any body its Content-Length gives, and sends
+/// back the response. Gives the base URL to request and a handle
+/// that joins to the request it got.
+#[cfg(test)]
+pub fn serve_once(response: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::<u8>::new();
+        let mut buf = [0; 1024];
+        let request_len = loop {
+            let text = String::from_utf8_lossy(&request);
+            if let Some(head_len) = text.find("\r\n\r\n") {
+                let content_len = text[..head_len]
+                    .lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+                    .map_or(0, |(_, len)| len.trim().parse::<usize>().unwrap());
+                if request.len() >= head_len + 4 + content_len {
+                    break head_len + 4 + content_len;
+                }
+            }
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "The connection closed before the request was complete.");
+            request.extend_from_slice(&buf[..n]);
+        };
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(request[..request_len].to_vec()).unwrap()
+    });
+    (url, server)
+}
+
+#[test]
+fn urls_are_split_into_host_port_and_prefix() {
+    let split = |url| split_url(url).unwrap();
+    assert_eq!(split("http://localhost"), ("localhost".to_string(), 80, "".to_string()));
+    assert_eq!(split("http://127.0.0.1:8080"), ("127.0.0.1".to_string(), 8080, "".to_string()));
+    assert_eq!(split("http://proxy:3128/aoc/"), ("proxy".to_string(), 3128, "/aoc".to_string()));
+    assert_eq!(split_url("http://localhost:http/aoc").unwrap_err().message, "Invalid port in http://localhost:http/aoc.");
+    assert!(split_url("http://localhost:65536").is_err());
+    assert!(split_url("https://adventofcode.com").is_err());
+}
+
+#[test]
+fn responses_give_status_and_body() {
+    let response = parse_response("HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\n\r\nNo input\r\n\r\nyet").unwrap();
+    assert_eq!((response.status, response.body.as_str()), (404, "No input\r\n\r\nyet"));
+    assert_eq!(parse_response("HTTP/1.0 200 OK\r\n\r\n").unwrap().body, "");
+    assert!(parse_response("HTTP/1.0 200 OK\r\n").is_err());
+    assert!(parse_response("HTTP/1.0 OK\r\n\r\nbody").is_err());
+}
+
+#[test]
+fn requests_send_headers_and_body() {
+    let (url, server) = serve_once("HTTP/1.0 201 Created\r\n\r\ndone");
+    let response = request(&format!("{}/prefix", url), "POST", "/path", &[("X-Test", "yes")], Some("a=1")).unwrap();
+    assert_eq!((response.status, response.body.as_str()), (201, "done"));
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /prefix/path HTTP/1.0\r\nHost: 127.0.0.1\r\nX-Test: yes\r\n"));
+    assert!(request.ends_with("Content-Length: 3\r\n\r\na=1"));
+}
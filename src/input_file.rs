@@ -1,27 +1,261 @@
+use std::fmt::Display;
 use std::fs;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
 
-/// Read the entire input file into a string.
+use crate::error::Error;
+
+/// A puzzle input, either a file to be read or text
+/// that is already in memory.
+pub enum PuzzleInput {
+    File(String),
+    Text(String)
+}
+
+impl PuzzleInput {
+    pub fn file(file_path: &str) -> PuzzleInput {
+        PuzzleInput::File(file_path.to_string())
+    }
+
+    pub fn text<S: Into<String>>(text: S) -> PuzzleInput {
+        PuzzleInput::Text(text.into())
+    }
+
+    /// Read all of stdin up front, so that the input can
+    /// still be read more than once.
+    pub fn stdin() -> Result<PuzzleInput, Error> {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)
+            .map_err(|e| Error::input(format!("Could not read the input from stdin ({}).", e)))?;
+        Ok(PuzzleInput::text(text))
+    }
+
+    /// Interpret a command line argument as an input,
+    /// where "-" means stdin and anything else is a file path.
+    pub fn from_arg(arg: &str) -> Result<PuzzleInput, Error> {
+        if arg == "-" {
+            PuzzleInput::stdin()
+        } else {
+            Ok(PuzzleInput::file(arg))
+        }
+    }
+}
+
+/// Read the entire input into a string.
 pub fn read_all_to_string(
-    file_path: &str
-) -> String {
-    return fs::read_to_string(file_path)
-    .expect("Could not read the input file.");
+    input: &PuzzleInput
+) -> Result<String, Error> {
+    match input {
+        PuzzleInput::File(file_path) => fs::read_to_string(file_path)
+            .map_err(|e| Error::input(format!("Could not read the input file {} ({}).", file_path, e))),
+        PuzzleInput::Text(text) => Ok(text.clone())
+    }
 }
 
-/// Read the entire input file into a vector, split
+/// Read the entire input into a vector, split
 /// by lines.
 pub fn read_lines(
-    file_path: &str
-) -> Vec<String> {
-    let file: File = File::open(file_path)
-        .expect("Problem opening input file.");
-    let reader = BufReader::new(file);
-    let mut lines = Vec::<String>::new();
-    for line in reader.lines() {
-        let line = line.expect("Issue reading line from input file.");
-        lines.push(line);
-    }
-    return lines;
-}
\ No newline at end of file
+    input: &PuzzleInput
+) -> Result<Vec<String>, Error> {
+    match input {
+        PuzzleInput::File(file_path) => {
+            let file: File = File::open(file_path)
+                .map_err(|e| Error::input(format!("Problem opening input file {} ({}).", file_path, e)))?;
+            let reader = BufReader::new(file);
+            let mut lines = Vec::<String>::new();
+            for line in reader.lines() {
+                let line = line
+                    .map_err(|e| Error::input(format!("Issue reading line from input file {} ({}).", file_path, e)))?;
+                lines.push(line);
+            }
+            Ok(lines)
+        },
+        PuzzleInput::Text(text) => Ok(text.lines().map(|line| line.to_string()).collect())
+    }
+}
+
+/// Parse each line of the input with the given function,
+/// pointing any error at the line that caused it.
+pub fn parse_each_line<T, F>(
+    input: &PuzzleInput,
+    parse_line: F
+) -> Result<Vec<T>, Error>
+where F: Fn(&str) -> Result<T, Error> {
+    parse_each(&read_lines(input)?, parse_line)
+}
+
+/// Parse each of the lines with the given function, pointing any
+/// error at the line that caused it, numbered from 1.
+pub fn parse_each<T, F>(
+    lines: &[String],
+    parse_line: F
+) -> Result<Vec<T>, Error>
+where F: Fn(&str) -> Result<T, Error> {
+    lines.iter()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.at_line(i + 1, line)))
+        .collect()
+}
+
+/// Read the input as sections separated by blank lines, such as
+/// a list of rules followed by a list of messages. Several blank
+/// lines in a row count as one, and blank lines at the start or
+/// end are ignored.
+pub fn read_sections(input: &PuzzleInput) -> Result<Vec<Vec<String>>, Error> {
+    Ok(numbered_sections(input)?.into_iter().map(|(_, section)| section).collect())
+}
+
+/// Parse each section of the input with the given function. Any error
+/// is pointed at a line counted from the top of the input, or at the
+/// start of the section if the parser didn't say which line.
+pub fn parse_sections<T, F>(
+    input: &PuzzleInput,
+    parse_section: F
+) -> Result<Vec<T>, Error>
+where F: Fn(&[String]) -> Result<T, Error> {
+    numbered_sections(input)?
+        .iter()
+        .map(|(first, section)| parse_section(section).map_err(|e| in_section(e, *first, section)))
+        .collect()
+}
+
+/// Parse an input made up of two sections, such as the dots and
+/// then the folds of a sheet of paper, with a function for each.
+/// Errors are pointed at lines as parse_sections does.
+pub fn parse_two_sections<A, B, FA, FB>(
+    input: &PuzzleInput,
+    parse_first: FA,
+    parse_second: FB
+) -> Result<(A, B), Error>
+where FA: Fn(&[String]) -> Result<A, Error>, FB: Fn(&[String]) -> Result<B, Error> {
+    match &numbered_sections(input)?[..] {
+        [(first, section1), (second, section2)] => Ok((
+            parse_first(section1).map_err(|e| in_section(e, *first, section1))?,
+            parse_second(section2).map_err(|e| in_section(e, *second, section2))?
+        )),
+        sections => Err(Error::parse(format!(
+            "Expected two sections separated by a blank line, found {}.", sections.len()
+        )))
+    }
+}
+
+/// The sections of the input, each with the number of its first line.
+fn numbered_sections(input: &PuzzleInput) -> Result<Vec<(usize, Vec<String>)>, Error> {
+    let mut sections = Vec::<(usize, Vec<String>)>::new();
+    let mut in_section = false;
+    for (i, line) in read_lines(input)?.into_iter().enumerate() {
+        if line.trim().is_empty() {
+            in_section = false;
+        } else if in_section {
+            sections.last_mut().unwrap().1.push(line);
+        } else {
+            sections.push((i + 1, vec![line]));
+            in_section = true;
+        }
+    }
+    Ok(sections)
+}
+
+/// Move an error from a section's parser to the line it
+/// refers to in the whole input.
+fn in_section(mut error: Error, first_line: usize, section: &[String]) -> Error {
+    match error.line.as_mut() {
+        Some(line) => {
+            *line += first_line - 1;
+            error
+        },
+        None => error.at_line(first_line, &section[0])
+    }
+}
+
+/// Parse each line of the input as a T, such as a number,
+/// pointing any error at the line that caused it.
+pub fn parse_lines<T: FromStr>(input: &PuzzleInput) -> Result<Vec<T>, Error>
+where T::Err: Display {
+    parse_each_line(input, parse_value)
+}
+
+/// Parse text as a T, ignoring any whitespace around it.
+pub fn parse_value<T: FromStr>(text: &str) -> Result<T, Error>
+where T::Err: Display {
+    text.trim()
+        .parse()
+        .map_err(|e| Error::parse(format!("Could not parse \"{}\" ({}).", text.trim(), e)))
+}
+
+/// Split text on the separator and parse each piece as a T,
+/// e.g. a line of comma separated numbers.
+pub fn split_parse<T: FromStr>(text: &str, separator: &str) -> Result<Vec<T>, Error>
+where T::Err: Display {
+    text.trim().split(separator).map(parse_value).collect()
+}
+
+/// Every integer in the text, in order, skipping anything else. A
+/// minus sign right before the digits makes the integer negative,
+/// unless it comes straight after another number, as in a range
+/// like 3-7.
+pub fn extract_ints<T: FromStr>(text: &str) -> Result<Vec<T>, Error>
+where T::Err: Display {
+    let bytes = text.as_bytes();
+    let mut ints = Vec::<T>::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let signed = i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_digit());
+        let start = if signed { i - 1 } else { i };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        ints.push(parse_value(&text[start..i])?);
+    }
+    Ok(ints)
+}
+
+#[test]
+fn read_lines_from_text() {
+    let input = PuzzleInput::text("199\n200\r\n208\n");
+    assert_eq!(read_lines(&input).unwrap(), vec!["199", "200", "208"]);
+}
+
+#[test]
+fn parse_each_line_points_error_at_line() {
+    let input = PuzzleInput::text("1\n2\nthree\n4");
+    let e = parse_each_line(&input, |line| Ok(line.parse::<u32>()?)).unwrap_err();
+    assert_eq!(e.line, Some(3));
+    assert_eq!(e.text.as_deref(), Some("three"));
+}
+
+#[test]
+fn extract_ints_handles_signs_and_ranges() {
+    let ints: Vec<i32> = extract_ints("Butterscotch: capacity -1, durability -2, flavor 6").unwrap();
+    assert_eq!(ints, vec![-1, -2, 6]);
+    let ints: Vec<i64> = extract_ints("x=-15..3, y=10-12").unwrap();
+    assert_eq!(ints, vec![-15, 3, 10, 12]);
+    assert!(extract_ints::<u32>("move -3").is_err());
+}
+
+#[test]
+fn split_parse_trims_pieces() {
+    assert_eq!(split_parse::<u8>("3,4, 3,1,2\n", ",").unwrap(), vec![3, 4, 3, 1, 2]);
+    let e = parse_each_line(&PuzzleInput::text("1,2\n3,x"), |line| split_parse::<u8>(line, ",")).unwrap_err();
+    assert_eq!(e.line, Some(2));
+}
+
+#[test]
+fn sections_point_errors_at_input_lines() {
+    let input = PuzzleInput::text("\n1,2\n3,4\n\n\nfold along y=7\nfold along x\n");
+    assert_eq!(read_sections(&input).unwrap(), vec![vec!["1,2", "3,4"], vec!["fold along y=7", "fold along x"]]);
+    let e = parse_two_sections(
+        &input,
+        |lines| parse_each(lines, |line| split_parse::<u8>(line, ",")),
+        |lines| parse_each(lines, |line| {
+            line.split_once('=').map(|(_, n)| n.to_string()).ok_or_else(|| Error::parse("No fold line."))
+        })
+    ).unwrap_err();
+    assert_eq!(e.line, Some(7));
+    assert_eq!(e.text.as_deref(), Some("fold along x"));
+}
@@ -0,0 +1,166 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::Error;
+use crate::http;
+use crate::input_file::PuzzleInput;
+
+/// Directory that puzzle inputs are cached in.
+pub const INPUTS_DIR: &str = "inputs";
+
+/// Environment variable naming a directory to copy inputs from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Environment variable holding the session token for fetching inputs.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable holding the base URL to fetch inputs from.
+pub const URL_VAR: &str = "AOC_URL";
+
+/// The http:// base URL in AOC_URL. There is no default, as only
+/// plain HTTP is supported and the puzzle site needs HTTPS, so it
+/// has to point at something like a local proxy for the site.
+pub fn url_from_env() -> Result<String, Error> {
+    env::var(URL_VAR).map_err(|_| Error::usage(format!(
+        "Set {} to an http:// base URL, such as a local proxy for https://adventofcode.com, which can't be reached directly.",
+        URL_VAR
+    )))
+}
+
+/// Somewhere puzzle inputs can be fetched from when
+/// they aren't in the cache yet.
+pub trait InputSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error>;
+}
+
+/// Inputs laid out as YEAR/dayN.txt under a directory,
+/// such as a shared checkout of everyone's inputs.
+pub struct DirectorySource {
+    dir: PathBuf
+}
+
+impl DirectorySource {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> DirectorySource {
+        DirectorySource { dir: dir.into() }
+    }
+}
+
+impl InputSource for DirectorySource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.dir.join(year.to_string()).join(format!("day{}.txt", day));
+        fs::read_to_string(&path)
+            .map_err(|e| Error::input(format!("Could not read {} ({}).", path.display(), e)))
+    }
+}
+
+/// Inputs served at BASE_URL/YEAR/day/N/input to the logged in
+/// user, as on the Advent of Code site.
+pub struct HttpSource {
+    base_url: String,
+    session: String
+}
+
+impl HttpSource {
+    pub fn new(base_url: &str, session: &str) -> HttpSource {
+        HttpSource { base_url: base_url.to_string(), session: session.to_string() }
+    }
+}
+
+impl InputSource for HttpSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let cookie = format!("session={}", self.session);
+        let path = format!("/{}/day/{}/input", year, day);
+        let response = http::request(&self.base_url, "GET", &path, &[("Cookie", &cookie)], None)?;
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err(Error::input(format!("The input for {} day {} is not available yet.", year, day))),
+            status => Err(Error::input(format!(
+                "Fetching {}{} failed with status {}; check the session token.",
+                self.base_url, path, status
+            )))
+        }
+    }
+}
+
+/// Puzzle inputs cached as YEAR/dayN.txt under a directory, fetched
+/// from the source and saved the first time each one is needed.
+pub struct InputCache {
+    dir: PathBuf,
+    source: Option<Box<dyn InputSource>>
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(dir: P, source: Option<Box<dyn InputSource>>) -> InputCache {
+        InputCache { dir: dir.into(), source }
+    }
+
+    /// Cache in the inputs directory, fetching from the directory in
+    /// AOC_INPUT_DIR if set, or otherwise over HTTP from AOC_URL if
+    /// both it and the session token in AOC_SESSION are set.
+    pub fn from_env() -> InputCache {
+        let source: Option<Box<dyn InputSource>> = if let Ok(dir) = env::var(INPUT_DIR_VAR) {
+            Some(Box::new(DirectorySource::new(dir)))
+        } else if let (Ok(session), Ok(url)) = (env::var(SESSION_VAR), url_from_env()) {
+            Some(Box::new(HttpSource::new(&url, &session)))
+        } else {
+            None
+        };
+        InputCache::new(INPUTS_DIR, source)
+    }
+
+    /// Where the input for the day is cached, e.g. inputs/2021/day14.txt.
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    /// The input for the day, fetching it into the cache if needed.
+    pub fn get(&self, year: u16, day: u8) -> Result<PuzzleInput, Error> {
+        let path = self.path(year, day);
+        if !path.exists() {
+            let source = self.source.as_ref().ok_or_else(|| Error::input(format!(
+                "No input file at {}; set {} to a directory of inputs, or {} and an http:// {} to fetch it.",
+                path.display(), INPUT_DIR_VAR, SESSION_VAR, URL_VAR
+            )))?;
+            let input = source.fetch(year, day)?;
+            let write_failed = |e: std::io::Error| Error::input(format!("Could not save {} ({}).", path.display(), e));
+            fs::create_dir_all(path.parent().unwrap()).map_err(write_failed)?;
+            fs::write(&path, input).map_err(write_failed)?;
+        }
+        Ok(PuzzleInput::file(&path.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn cache_fetches_missing_inputs_once() {
+    use crate::input_file::read_all_to_string;
+
+    let shared = temp_dir("shared");
+    let cache_dir = temp_dir("cache");
+    fs::create_dir_all(shared.join("2021")).unwrap();
+    fs::write(shared.join("2021/day1.txt"), "199\n200\n").unwrap();
+    let cache = InputCache::new(&cache_dir, Some(Box::new(DirectorySource::new(&shared))));
+
+    let input = cache.get(2021, 1).unwrap();
+    assert_eq!(read_all_to_string(&input).unwrap(), "199\n200\n");
+    assert!(cache.path(2021, 1).exists());
+    fs::remove_dir_all(&shared).unwrap();
+    assert_eq!(read_all_to_string(&cache.get(2021, 1).unwrap()).unwrap(), "199\n200\n");
+    assert!(cache.get(2021, 2).is_err());
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn http_source_sends_session_cookie() {
+    let (url, server) = http::serve_once("HTTP/1.0 200 OK\r\n\r\n3,4,3,1,2\n");
+    let source = HttpSource::new(&url, "abc123");
+    assert_eq!(source.fetch(2021, 6).unwrap(), "3,4,3,1,2\n");
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /2021/day/6/input HTTP/1.0\r\n"));
+    assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+}
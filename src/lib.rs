@@ -0,0 +1,36 @@
+//! Solutions to Advent of Code puzzles, along with the utilities
+//! they share and the machinery for running and checking them.
+
+pub mod year2015; pub mod year2016; pub mod year2021; pub mod year2024; pub mod year2025;
+pub mod input_file;
+pub mod captures;
+pub mod inputs;
+pub mod http;
+pub mod submit;
+pub mod data_structs;
+pub mod search;
+pub mod binary;
+pub mod solution;
+pub mod answer;
+pub mod runner;
+pub mod schedule;
+pub mod cancel;
+pub mod timing;
+pub mod expected;
+pub mod error;
+pub mod scaffold;
+#[cfg(test)]
+mod example_tests;
+
+use solution::Registry;
+
+/// Build the registry of every implemented solution.
+pub fn build_registry() -> Registry {
+    let mut registry = Registry::new();
+    year2015::register(&mut registry);
+    year2016::register(&mut registry);
+    year2021::register(&mut registry);
+    year2024::register(&mut registry);
+    year2025::register(&mut registry);
+    registry
+}
@@ -1,33 +1,339 @@
 use std::env;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-mod year2015; mod year2016; mod year2021; mod year2024; mod year2025;
-mod input_file;
-mod data_structs;
-mod binary;
+use aoc::build_registry;
+use aoc::error::{Error, EXIT_WRONG_ANSWER};
+use aoc::expected::{ExpectedAnswers, ANSWERS_FILE};
+use aoc::input_file::PuzzleInput;
+use aoc::inputs::{url_from_env, InputCache, SESSION_VAR};
+use aoc::runner::{self, Format, Selection, Spec};
+use aoc::schedule::{self, Schedule, Task};
+use aoc::solution::Registry;
+use aoc::submit::{self, Attempt, History, Verdict, HISTORY_FILE};
+use aoc::timing::format_duration;
 
 fn main() {
     // Collect command line arguments
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 5 {
-        panic!("Usage: {} YEAR DAY PART INPUT_FILE", &args[0]);
-    }
-
-    let year: u16 = args[1].parse().expect("Error parsing argument year.");
-    let day: u8 = args[2].parse().expect("Error parsing argument day.");
-    let part: u8 = args[3].parse().expect("Error parsing argument part.");
-    if part != 1 && part != 2 {
-        panic!("Part must be 1 or 2.");
-    }
-    let input_file_path = &args[4];
-
-    match year {
-        2015 => year2015::solve(day, part, input_file_path),
-        2016 => year2016::solve(day, part, input_file_path),
-        2021 => year2021::solve(day, part, input_file_path),
-        2024 => year2024::solve(day, part, input_file_path),
-        2025 => year2025::solve(day, part, input_file_path),
-        _ => {
-            eprintln!("Solutions to year {} not yet implemented.", year);
+    let mut args: Vec<String> = env::args().collect();
+    let registry = build_registry();
+    let result = take_options(&mut args).and_then(|options| {
+        match args.get(1).map(|a| a.as_str()) {
+            Some("run") => run_many(&registry, &args, &options),
+            Some("bench") => bench(&registry, &args, &options),
+            Some("verify") => verify(&registry, &args, &options),
+            Some("new") => new_day(&args),
+            Some("submit") => submit(&registry, &args, &options),
+            _ => run_one(&registry, &args, &options)
         }
+    });
+    match result {
+        Ok(0) => (),
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Options that can be given anywhere after the program name.
+struct Options {
+    show_time: bool,
+    format: Format,
+    schedule: Schedule
+}
+
+/// Remove the options from the arguments.
+fn take_options(args: &mut Vec<String>) -> Result<Options, Error> {
+    let show_time = take_flag(args, "--time");
+    let format = match take_value(args, "--format")? {
+        Some(format) => Format::from_arg(&format)
+            .ok_or_else(|| Error::usage(format!("Unknown format {}, expected table or json.", format)))?,
+        None => Format::Table
+    };
+    let mut schedule = Schedule::default();
+    if let Some(jobs) = take_value(args, "--jobs")? {
+        schedule.jobs = parse_arg(&jobs, "jobs")?;
+        if schedule.jobs == 0 {
+            return Err(Error::usage("Jobs must be at least 1."));
+        }
+    }
+    if let Some(secs) = take_value(args, "--timeout")? {
+        schedule.timeout = Some(Duration::from_secs(parse_arg(&secs, "timeout")?));
+    }
+    if let Some(ms) = take_value(args, "--budget")? {
+        schedule.budget = Some(Duration::from_millis(parse_arg(&ms, "budget")?));
+    }
+    Ok(Options { show_time, format, schedule })
+}
+
+/// Remove the flag from the arguments, returning
+/// whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+/// Remove the option and the value following it
+/// from the arguments, returning the value.
+fn take_value(args: &mut Vec<String>, option: &str) -> Result<Option<String>, Error> {
+    match args.iter().position(|arg| arg == option) {
+        Some(i) if i + 1 < args.len() => {
+            let value = args.remove(i + 1);
+            args.remove(i);
+            Ok(Some(value))
+        },
+        Some(_) => Err(Error::usage(format!("Missing value for {}.", option))),
+        None => Ok(None)
+    }
+}
+
+/// Run one or both parts of a single day against the given input
+/// file, or the cached input if there isn't one. The input is only
+/// parsed once when running both parts, and a part that runs past
+/// the timeout is given up on.
+fn run_one(registry: &Registry, args: &[String], options: &Options) -> Result<i32, Error> {
+    if args.len() < 3 || args.len() > 5 {
+        return Err(Error::usage(usage(&args[0])));
+    }
+    let task = task(registry, &args[1..])?;
+    let (year, day, parts) = (task.solution.year(), task.solution.day(), &task.parts);
+    let results = schedule::run_task(&task, options.schedule.timeout);
+
+    if options.format == Format::Json {
+        for (&part, result) in parts.iter().zip(&results) {
+            println!("{}", runner::json_result(year, day, part, result).dump());
+        }
+        return Ok(results.iter().find_map(|r| r.as_ref().err()).map_or(0, |e| e.exit_code()));
+    }
+    if let [result] = &results[..] {
+        let (answer, timing) = result.clone()?;
+        println!("{}", answer);
+        if options.show_time {
+            eprintln!(
+                "Parse: {}, solve: {}",
+                format_duration(timing.parse), format_duration(timing.solve)
+            );
+        }
+        return Ok(0);
+    }
+
+    // Label each part's answer, keeping going past a part that fails.
+    let mut code = 0;
+    for (&part, result) in parts.iter().zip(results) {
+        match result {
+            Ok((answer, timing)) => {
+                let answer = answer.to_string();
+                let label = format!("Part {}: ", part);
+                let indent = " ".repeat(label.len());
+                println!("{}{}", label, answer.replace('\n', &format!("\n{}", indent)));
+                if options.show_time {
+                    if part == parts[0] {
+                        eprintln!("Parse: {}", format_duration(timing.parse));
+                    }
+                    eprintln!("Part {} solve: {}", part, format_duration(timing.solve));
+                }
+            },
+            Err(e) => {
+                eprintln!("Part {}: Error: {}", part, e);
+                code = e.exit_code();
+            }
+        }
+    }
+    Ok(code)
+}
+
+/// Work out the answer to a part and submit it, unless it's
+/// known to be wrong, then record how it went in the history.
+fn submit(registry: &Registry, args: &[String], options: &Options) -> Result<i32, Error> {
+    if (args.len() != 5 && args.len() != 6) || args[4].parse::<u8>().is_err() {
+        return Err(Error::usage(usage(&args[0])));
+    }
+    let task = task(registry, &args[2..])?;
+    let (year, day, part) = (task.solution.year(), task.solution.day(), task.parts[0]);
+    let answer = schedule::run_task(&task, options.schedule.timeout).remove(0)?.0.to_string();
+    if answer.contains('\n') {
+        return Err(Error::usage(format!("The answer spans several lines; read it and submit it by hand.\n{}", answer)));
+    }
+    let session = env::var(SESSION_VAR)
+        .map_err(|_| Error::usage(format!("Set {} to the session token to submit answers.", SESSION_VAR)))?;
+    let url = url_from_env()?;
+
+    let mut history = History::load(HISTORY_FILE)?;
+    let time = submit::now();
+    if let Some(reason) = history.refusal(year, day, part, &answer, time) {
+        eprintln!("Not submitting {}: {}", answer, reason);
+        return Ok(EXIT_WRONG_ANSWER);
+    }
+    let verdict = submit::submit(&url, &session, year, day, part, &answer)?;
+    history.record(Attempt { year, day, part, answer: answer.clone(), verdict, time });
+    history.save(HISTORY_FILE)?;
+    println!("{}: {}", answer, verdict.name());
+    match verdict {
+        Verdict::Correct | Verdict::AlreadySolved => Ok(0),
+        _ => Ok(EXIT_WRONG_ANSWER)
+    }
+}
+
+/// Find the solution, parts and input for YEAR DAY [PART|both]
+/// [INPUT_FILE|-], running every part if PART is left out and
+/// using the cached input if no file is given.
+fn task(registry: &Registry, args: &[String]) -> Result<Arc<Task>, Error> {
+    let year: u16 = parse_arg(&args[0], "year")?;
+    let day: u8 = parse_arg(&args[1], "day")?;
+    let solution = registry.shared(year, day).ok_or_else(|| missing(registry, year, day))?;
+    let (parts, input_arg) = match args.get(2).map(|a| a.as_str()) {
+        Some("both") => (solution.parts(), args.get(3)),
+        Some(arg) if arg.parse::<u8>().is_ok() => {
+            let part: u8 = parse_arg(arg, "part")?;
+            if part != 1 && part != 2 {
+                return Err(Error::usage("Part must be 1 or 2."));
+            }
+            if !solution.has_part(part) {
+                return Err(Error::usage(format!("{} day {} has no part {}.", year, day, part)));
+            }
+            (vec![part], args.get(3))
+        },
+        _ if args.len() > 3 => return Err(Error::usage(format!("Invalid part {}.", args[2]))),
+        _ => (solution.parts(), args.get(2))
+    };
+    let input = match input_arg {
+        Some(arg) => PuzzleInput::from_arg(arg)?,
+        None => InputCache::from_env().get(year, day).map_err(|e| e.for_day(year, day))?
+    };
+    Ok(Arc::new(Task { solution, input, parts }))
+}
+
+/// Run every part of the selected days, reading inputs
+/// from the conventional inputs directory.
+fn run_many(registry: &Registry, args: &[String], options: &Options) -> Result<i32, Error> {
+    let selection = parse_selection(&args[0], &args[2..])?;
+    check_selection(registry, &selection)?;
+    Ok(runner::run_selection(
+        registry, &selection, &InputCache::from_env(), &options.schedule, options.show_time, options.format
+    ).exit_code())
+}
+
+/// Run each part of the selected days several times and
+/// report how long they took.
+fn bench(registry: &Registry, args: &[String], options: &Options) -> Result<i32, Error> {
+    if args.len() < 3 {
+        return Err(Error::usage(usage(&args[0])));
+    }
+    if options.format != Format::Table {
+        return Err(Error::usage("Benchmarks can only be printed as a table."));
+    }
+    let runs: usize = parse_arg(&args[2], "runs")?;
+    if runs == 0 {
+        return Err(Error::usage("Runs must be at least 1."));
+    }
+    let selection = parse_selection(&args[0], &args[3..])?;
+    check_selection(registry, &selection)?;
+    runner::bench_selection(registry, &selection, &InputCache::from_env(), runs)?;
+    Ok(0)
+}
+
+/// Check the answers of the selected days (all by default) against
+/// the expected answers file, exiting with an error if any are wrong.
+fn verify(registry: &Registry, args: &[String], options: &Options) -> Result<i32, Error> {
+    let selection = if args.len() == 2 {
+        Selection::All
+    } else {
+        parse_selection(&args[0], &args[2..])?
+    };
+    check_selection(registry, &selection)?;
+    let expected = ExpectedAnswers::load(ANSWERS_FILE)?;
+    Ok(runner::verify_selection(
+        registry, &selection, &InputCache::from_env(), &expected, &options.schedule, options.format
+    ).exit_code())
+}
+
+/// Generate the module for a new day and register it.
+fn new_day(args: &[String]) -> Result<i32, Error> {
+    if args.len() != 4 {
+        return Err(Error::usage(usage(&args[0])));
+    }
+    let year: u16 = parse_arg(&args[2], "year")?;
+    let day: u8 = parse_arg(&args[3], "day")?;
+    for path in aoc::scaffold::new_day(Path::new("."), year, day)? {
+        println!("Wrote {}", path);
+    }
+    Ok(0)
+}
+
+/// Parse a numeric command line argument.
+fn parse_arg<T: FromStr>(arg: &str, name: &str) -> Result<T, Error> {
+    arg.parse().map_err(|_| Error::usage(format!("Error parsing argument {}: {}", name, arg)))
+}
+
+/// Parse --all, YEAR, YEAR DAY or a list of SPECs into a Selection.
+fn parse_selection(program: &str, args: &[String]) -> Result<Selection, Error> {
+    match args {
+        [] => Err(Error::usage(usage(program))),
+        [all] if all == "--all" => Ok(Selection::All),
+        [year] if year.parse::<u16>().is_ok() => Ok(Selection::Year(parse_arg(year, "year")?)),
+        [year, day] if year.parse::<u16>().is_ok() && day.parse::<u8>().is_ok() => {
+            Ok(Selection::Day(parse_arg(year, "year")?, parse_arg(day, "day")?))
+        },
+        specs => specs.iter()
+            .map(|spec| Spec::from_arg(spec).ok_or_else(|| Error::usage(format!(
+                "Invalid selection {}, expected YEAR[/DAY[-DAY]][/PART].", spec
+            ))))
+            .collect::<Result<Vec<Spec>, Error>>()
+            .map(Selection::List)
+    }
+}
+
+/// Check that there is something to run for the selection,
+/// explaining what is available if not.
+fn check_selection(registry: &Registry, selection: &Selection) -> Result<(), Error> {
+    match *selection {
+        Selection::Year(year) if registry.days(year).is_empty() => Err(missing(registry, year, 0)),
+        Selection::Day(year, day) if registry.get(year, day).is_none() => Err(missing(registry, year, day)),
+        Selection::List(_) if registry.iter().all(|s| selection.parts(s).is_empty()) => {
+            Err(Error::usage("No implemented solutions match the selection."))
+        },
+        _ => Ok(())
+    }
+}
+
+fn usage(program: &str) -> String {
+    format!(
+        "Usage: {0} YEAR DAY [PART|both] [INPUT_FILE|-] [--time] [--format table|json] [--timeout SECS]\n       \
+         {0} run --all|YEAR|YEAR DAY|SPEC... [--time] [--format table|json] [SCHEDULE]\n       \
+         {0} bench RUNS --all|YEAR|YEAR DAY|SPEC...\n       \
+         {0} verify [--all|YEAR|YEAR DAY|SPEC...] [--format table|json] [SCHEDULE]\n       \
+         {0} submit YEAR DAY PART [INPUT_FILE|-] [--timeout SECS]\n       \
+         {0} new YEAR DAY\n\
+         SPEC is YEAR[/DAY[-DAY]][/PART], e.g. 2015/1-10 2021/6/2\n\
+         SCHEDULE is [--jobs N] [--timeout SECS] [--budget MS]\n\
+         --timeout gives up on a part after SECS seconds. A part given up on keeps a CPU\n\
+         busy until it next checks for cancellation or finishes, so more than N threads\n\
+         may be busy with --jobs N.",
+        program
+    )
+}
+
+/// Explain what is available when asked for a day
+/// that has no solution.
+fn missing(registry: &Registry, year: u16, day: u8) -> Error {
+    let days = registry.days(year);
+    if days.is_empty() {
+        let years: Vec<String> = registry.years().iter().map(|y| y.to_string()).collect();
+        Error::usage(format!(
+            "Solutions to year {} not yet implemented. Implemented years: {}",
+            year, years.join(", ")
+        ))
+    } else {
+        let days: Vec<String> = days.iter().map(|d| d.to_string()).collect();
+        Error::usage(format!(
+            "Solution to {} day {} not yet implemented. Implemented days: {}",
+            year, day, days.join(", ")
+        ))
     }
 }
@@ -0,0 +1,505 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use json::JsonValue;
+
+use crate::answer::Answer;
+use crate::error::{Error, ErrorKind, EXIT_PUZZLE, EXIT_TIMEOUT, EXIT_WRONG_ANSWER};
+use crate::expected::ExpectedAnswers;
+use crate::inputs::InputCache;
+use crate::schedule::{run_tasks, PartResult, Schedule, Task};
+use crate::solution::{AnySolution, Registry};
+use crate::timing::{format_duration, millis, Stats, Timing};
+
+/// How the results of running solutions are printed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A table to be read in the terminal.
+    Table,
+    /// A JSON object on its own line for each part that is run.
+    Json
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Option<Format> {
+        match arg {
+            "table" => Some(Format::Table),
+            "json" => Some(Format::Json),
+            _ => None
+        }
+    }
+}
+
+/// Which of the implemented solutions to run.
+pub enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+    /// Any day or part matching one of the specs.
+    List(Vec<Spec>)
+}
+
+impl Selection {
+    /// The selected parts of the solution, if any, in order.
+    pub fn parts(&self, solution: &dyn AnySolution) -> Vec<u8> {
+        let (year, day) = (solution.year(), solution.day());
+        solution.parts().into_iter()
+            .filter(|&part| match self {
+                Selection::All => true,
+                Selection::Year(y) => year == *y,
+                Selection::Day(y, d) => year == *y && day == *d,
+                Selection::List(specs) => specs.iter().any(|spec| spec.includes(year, day, part))
+            })
+            .collect()
+    }
+}
+
+/// A year, optionally narrowed down to a range of days and to
+/// one part, written as YEAR[/DAY[-DAY]][/PART], e.g. 2021/1-10
+/// or 2015/22/2. A range must run from the earlier day to the later.
+pub struct Spec {
+    year: u16,
+    days: RangeInclusive<u8>,
+    part: Option<u8>
+}
+
+impl Spec {
+    pub fn from_arg(arg: &str) -> Option<Spec> {
+        let mut fields = arg.split('/');
+        let year = fields.next()?.parse().ok()?;
+        let days = match fields.next() {
+            Some(days) => match days.split_once('-') {
+                Some((first, last)) => first.parse().ok()?..=last.parse().ok()?,
+                None => {
+                    let day = days.parse().ok()?;
+                    day..=day
+                }
+            },
+            None => 1..=25
+        };
+        let part = match fields.next() {
+            Some(part) => Some(part.parse().ok().filter(|part| *part == 1 || *part == 2)?),
+            None => None
+        };
+        if fields.next().is_some() || days.is_empty() {
+            return None;
+        }
+        Some(Spec { year, days, part })
+    }
+
+    fn includes(&self, year: u16, day: u8, part: u8) -> bool {
+        self.year == year && self.days.contains(&day) && self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// How a run over several days went: the number of parts
+/// that failed with an error or timed out, when verifying, the
+/// number that gave the wrong answer, and the parts that took
+/// longer than the time budget.
+#[derive(Default)]
+pub struct Summary {
+    pub errors: usize,
+    pub timeouts: usize,
+    pub wrong: usize,
+    pub over_budget: Vec<(u16, u8, u8, Duration)>
+}
+
+impl Summary {
+    /// Exit code for the run, 0 if everything went well.
+    pub fn exit_code(&self) -> i32 {
+        if self.errors > 0 {
+            EXIT_PUZZLE
+        } else if self.timeouts > 0 {
+            EXIT_TIMEOUT
+        } else if self.wrong > 0 {
+            EXIT_WRONG_ANSWER
+        } else {
+            0
+        }
+    }
+
+    /// Count the result of a part, returning whether
+    /// it took longer than the budget.
+    fn add(&mut self, year: u16, day: u8, part: u8, result: &PartResult, budget: Option<Duration>) -> bool {
+        match (result, budget) {
+            (Err(e), _) if e.kind == ErrorKind::Timeout => self.timeouts += 1,
+            (Err(_), _) => self.errors += 1,
+            (Ok((_, timing)), Some(budget)) if timing.parse + timing.solve > budget => {
+                self.over_budget.push((year, day, part, timing.parse + timing.solve));
+                return true;
+            },
+            _ => ()
+        }
+        false
+    }
+
+    /// List the parts that went over the budget after a table.
+    fn print_over_budget(&self, budget: Option<Duration>) {
+        if let (Some(budget), false) = (budget, self.over_budget.is_empty()) {
+            println!();
+            println!("Over the {} budget:", format_duration(budget));
+            for (year, day, part, took) in &self.over_budget {
+                println!("{:>4}  {:>3}  {:>4}  {:>12}", year, day, part, format_duration(*took));
+            }
+        }
+    }
+}
+
+/// Run the selected parts of every selected solution against its
+/// input, parsing it once per day, and print a table of the answers,
+/// along with how long each part took if show_time is set, or a line
+/// of JSON for each part. Days are run as the schedule says, but
+/// printed in order, followed by any parts that went over the budget.
+/// Days whose input can't be found are skipped, and parts that fail
+/// are reported along with the rest.
+pub fn run_selection(
+    registry: &Registry,
+    selection: &Selection,
+    inputs: &InputCache,
+    schedule: &Schedule,
+    show_time: bool,
+    format: Format
+) -> Summary {
+    let mut summary = Summary::default();
+    if format == Format::Table {
+        if show_time {
+            println!("{:>4}  {:>3}  {:>4}  {:>12}  {:>12}  Answer", "Year", "Day", "Part", "Parse", "Solve");
+        } else {
+            println!("{:>4}  {:>3}  {:>4}  Answer", "Year", "Day", "Part");
+        }
+    }
+    run_tasks(tasks(registry, selection, inputs), schedule, |task, results| {
+        let (year, day) = (task.solution.year(), task.solution.day());
+        for (&part, result) in task.parts.iter().zip(results) {
+            let over_budget = summary.add(year, day, part, &result, schedule.budget);
+            match (format, result) {
+                (Format::Json, result) => {
+                    let mut json = json_result(year, day, part, &result);
+                    if schedule.budget.is_some() {
+                        json["over_budget"] = over_budget.into();
+                    }
+                    println!("{}", json.dump());
+                },
+                (Format::Table, Ok((answer, timing))) => {
+                    let timing = if show_time { Some(timing) } else { None };
+                    print_row(year, day, part, timing, &answer);
+                },
+                (Format::Table, Err(e)) => {
+                    let prefix = format!("{:>4}  {:>3}  {:>4}  ", year, day, part);
+                    print_lines_after(&prefix, &format!("{} {}", error_status(&e), e));
+                }
+            }
+        }
+    });
+    if format == Format::Table {
+        summary.print_over_budget(schedule.budget);
+    }
+    summary
+}
+
+/// Run the selected parts of every selected solution the given
+/// number of times, one after another so they don't slow each other
+/// down, and print the min, median and max durations of parsing and
+/// solving. Stops at the first part that fails.
+pub fn bench_selection(
+    registry: &Registry,
+    selection: &Selection,
+    inputs: &InputCache,
+    runs: usize
+) -> Result<(), Error> {
+    println!(
+        "{:>4}  {:>3}  {:>4}  {:>38}  {:>38}",
+        "Year", "Day", "Part", "Parse (min / median / max)", "Solve (min / median / max)"
+    );
+    for task in tasks(registry, selection, inputs) {
+        let solution = &task.solution;
+        for &part in &task.parts {
+            let timings = (0..runs)
+                .map(|_| solution.run_timed(part, &task.input).map(|(_, timing)| timing))
+                .collect::<Result<Vec<Timing>, Error>>()?;
+            let parse: Vec<_> = timings.iter().map(|t| t.parse).collect();
+            let solve: Vec<_> = timings.iter().map(|t| t.solve).collect();
+            println!(
+                "{:>4}  {:>3}  {:>4}  {}  {}",
+                solution.year(), solution.day(), part,
+                format_stats(&Stats::from_durations(&parse)),
+                format_stats(&Stats::from_durations(&solve))
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Run the selected parts of every selected solution, parsing each
+/// input once, and compare the answers to the expected ones, printing
+/// PASS, FAIL, MISSING (no expected answer stored), ERROR or TIMEOUT
+/// for each, either in a table or as a line of JSON with the status and
+/// expected answer added. Days are run as the schedule says, but printed
+/// in order, followed by any parts that went over the budget.
+pub fn verify_selection(
+    registry: &Registry,
+    selection: &Selection,
+    inputs: &InputCache,
+    expected: &ExpectedAnswers,
+    schedule: &Schedule,
+    format: Format
+) -> Summary {
+    if format == Format::Table {
+        println!("{:>4}  {:>3}  {:>4}  {:<7}  Answer", "Year", "Day", "Part", "Result");
+    }
+    let mut summary = Summary::default();
+    run_tasks(tasks(registry, selection, inputs), schedule, |task, results| {
+        let (year, day) = (task.solution.year(), task.solution.day());
+        for (&part, result) in task.parts.iter().zip(results) {
+            let over_budget = summary.add(year, day, part, &result, schedule.budget);
+            let (status, detail) = match &result {
+                Ok((answer, _)) => check_answer(&answer.to_string(), expected.get(year, day, part)),
+                Err(e) => (error_status(e), e.to_string())
+            };
+            if status == "FAIL" {
+                summary.wrong += 1;
+            }
+            match format {
+                Format::Table => {
+                    let prefix = format!("{:>4}  {:>3}  {:>4}  {:<7}  ", year, day, part, status);
+                    print_lines_after(&prefix, &detail);
+                },
+                Format::Json => {
+                    let mut json = json_result(year, day, part, &result);
+                    json["status"] = status.to_lowercase().into();
+                    if let Some(expected) = expected.get(year, day, part) {
+                        json["expected"] = expected.into();
+                    }
+                    if schedule.budget.is_some() {
+                        json["over_budget"] = over_budget.into();
+                    }
+                    println!("{}", json.dump());
+                }
+            }
+        }
+    });
+    if format == Format::Table {
+        summary.print_over_budget(schedule.budget);
+    }
+    summary
+}
+
+/// The selected parts of each selected solution along with its
+/// input, skipping any whose input isn't cached and can't be fetched.
+fn tasks(registry: &Registry, selection: &Selection, inputs: &InputCache) -> Vec<Task> {
+    registry.iter()
+        .filter_map(|solution| {
+            let (year, day) = (solution.year(), solution.day());
+            let parts = selection.parts(solution);
+            if parts.is_empty() {
+                return None;
+            }
+            match inputs.get(year, day) {
+                Ok(input) => Some(Task { solution: registry.shared(year, day)?, input, parts }),
+                Err(e) => {
+                    eprintln!("Skipping {} day {}: {}", year, day, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// The result of running one part as a JSON object, with the answer
+/// and timings in milliseconds if it succeeded, or the error if not.
+/// Every result has the same keys, with null for those that don't apply.
+pub fn json_result(year: u16, day: u8, part: u8, result: &PartResult) -> JsonValue {
+    let mut json = JsonValue::new_object();
+    json["year"] = year.into();
+    json["day"] = day.into();
+    json["part"] = part.into();
+    match result {
+        Ok((answer, timing)) => {
+            json["status"] = "ok".into();
+            json["answer"] = answer.into();
+            json["parse_ms"] = json_millis(timing.parse);
+            json["solve_ms"] = json_millis(timing.solve);
+            json["error"] = JsonValue::Null;
+        },
+        Err(e) => {
+            json["status"] = error_status(e).to_lowercase().into();
+            json["answer"] = JsonValue::Null;
+            json["parse_ms"] = JsonValue::Null;
+            json["solve_ms"] = JsonValue::Null;
+            json["error"] = e.to_string().into();
+        }
+    }
+    json
+}
+
+/// How a part that gave an answer is reported: PASS if it matches the
+/// expected answer, FAIL with the expected answer if not, or MISSING
+/// if no answer is expected.
+fn check_answer(answer: &str, expected: Option<&str>) -> (&'static str, String) {
+    match expected {
+        Some(expected) if expected.trim() == answer.trim() => ("PASS", answer.to_string()),
+        Some(expected) => ("FAIL", format!("{}\n(expected {})", answer, expected)),
+        None => ("MISSING", answer.to_string())
+    }
+}
+
+/// How a part that failed is reported: TIMEOUT if it took
+/// too long, or ERROR otherwise.
+fn error_status(error: &Error) -> &'static str {
+    match error.kind {
+        ErrorKind::Timeout => "TIMEOUT",
+        _ => "ERROR"
+    }
+}
+
+/// Milliseconds rounded to the nearest microsecond, to
+/// keep the JSON readable.
+fn json_millis(duration: Duration) -> JsonValue {
+    ((millis(duration) * 1000.0).round() / 1000.0).into()
+}
+
+/// Print one row of the answers table. Answers spanning several
+/// lines are lined up underneath the answer column.
+fn print_row(year: u16, day: u8, part: u8, timing: Option<Timing>, answer: &Answer) {
+    let mut prefix = format!("{:>4}  {:>3}  {:>4}  ", year, day, part);
+    if let Some(timing) = timing {
+        prefix += &format!(
+            "{:>12}  {:>12}  ",
+            format_duration(timing.parse), format_duration(timing.solve)
+        );
+    }
+    print_lines_after(&prefix, &answer.to_string());
+}
+
+/// Print text after the prefix, lining up any further lines
+/// of the text underneath the first.
+fn print_lines_after(prefix: &str, text: &str) {
+    let mut lines = text.lines();
+    println!("{}{}", prefix, lines.next().unwrap_or(""));
+    for line in lines {
+        println!("{:width$}{}", "", line, width = prefix.len());
+    }
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "{:>12} / {:>12} / {:>12}",
+        format_duration(stats.min), format_duration(stats.median), format_duration(stats.max)
+    )
+}
+
+/// A day whose parts answer with the first and second words of its input.
+#[cfg(test)]
+struct Echo(u16, u8);
+
+#[cfg(test)]
+impl crate::solution::Solution for Echo {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        self.0
+    }
+
+    fn day(&self) -> u8 {
+        self.1
+    }
+
+    fn parse(&self, input: &crate::input_file::PuzzleInput) -> Result<Vec<String>, Error> {
+        Ok(crate::input_file::read_all_to_string(input)?.split_whitespace().map(String::from).collect())
+    }
+
+    fn part1(&self, words: &Vec<String>) -> Result<Answer, Error> {
+        Ok(Answer::from(words[0].as_str()))
+    }
+
+    fn part2(&self, words: &Vec<String>) -> Result<Answer, Error> {
+        Ok(Answer::from(words[1].as_str()))
+    }
+}
+
+#[test]
+fn check_answer_passes_fails_or_is_missing() {
+    assert_eq!(check_answer("26", Some("26\n")), ("PASS", "26".to_string()));
+    assert_eq!(check_answer("26", Some("27")), ("FAIL", "26\n(expected 27)".to_string()));
+    assert_eq!(check_answer("26", None), ("MISSING", "26".to_string()));
+}
+
+#[test]
+fn verify_exits_with_wrong_answer_only_when_an_answer_fails() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("2021")).unwrap();
+    fs::write(dir.join("2021/day6.txt"), "26 5934\n").unwrap();
+    let inputs = InputCache::new(&dir, None);
+    let mut registry = Registry::new();
+    registry.add(Echo(2021, 6));
+
+    let cases = [
+        (r#"{"2021": {"6": {"1": 26, "2": 5934}}}"#, 0, 0),
+        (r#"{"2021": {"6": {"1": 26, "2": 5935}}}"#, 1, EXIT_WRONG_ANSWER),
+        (r#"{"2021": {"6": {"1": 26}}}"#, 0, 0)
+    ];
+    for (answers, wrong, exit_code) in cases.iter() {
+        let path = dir.join("answers.json");
+        fs::write(&path, answers).unwrap();
+        let expected = ExpectedAnswers::load(&path.display().to_string()).unwrap();
+        let summary = verify_selection(&registry, &Selection::All, &inputs, &expected, &Schedule::default(), Format::Json);
+        assert_eq!((summary.errors, summary.timeouts, summary.wrong), (0, 0, *wrong), "{}", answers);
+        assert_eq!(summary.exit_code(), *exit_code, "{}", answers);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn specs_select_the_parts_they_cover() {
+    let solutions = [Echo(2015, 1), Echo(2015, 10), Echo(2015, 11), Echo(2016, 1), Echo(2021, 6)];
+    let cases: [(&str, Option<[Vec<u8>; 5]>); 7] = [
+        ("2015", Some([vec![1, 2], vec![1, 2], vec![1, 2], vec![], vec![]])),
+        ("2015/1-10", Some([vec![1, 2], vec![1, 2], vec![], vec![], vec![]])),
+        ("2021/6/2", Some([vec![], vec![], vec![], vec![], vec![2]])),
+        ("2021/6/3", None),
+        ("2021/x", None),
+        ("2021/1/1/1", None),
+        ("2015/10-1", None)
+    ];
+    for (arg, expected) in cases.iter() {
+        let selected = Spec::from_arg(arg).map(|spec| {
+            let selection = Selection::List(vec![spec]);
+            solutions.iter().map(|solution| selection.parts(solution)).collect::<Vec<_>>()
+        });
+        assert_eq!(selected, expected.clone().map(Vec::from), "{}", arg);
+    }
+}
+
+#[cfg(test)]
+fn json_keys(json: &JsonValue) -> Vec<&str> {
+    json.entries().map(|(key, _)| key).collect()
+}
+
+#[test]
+fn json_result_gives_the_answer_and_timings() {
+    let timing = Timing { parse: Duration::from_micros(1500), solve: Duration::from_nanos(2_000_400) };
+    let json = json_result(2021, 6, 1, &Ok((Answer::from(5934u64), timing)));
+    assert_eq!(json_keys(&json), ["year", "day", "part", "status", "answer", "parse_ms", "solve_ms", "error"]);
+    assert_eq!(
+        json.dump(),
+        r#"{"year":2021,"day":6,"part":1,"status":"ok","answer":5934,"parse_ms":1.5,"solve_ms":2,"error":null}"#
+    );
+}
+
+#[test]
+fn json_result_gives_the_error_or_timeout() {
+    let keys = ["year", "day", "part", "status", "answer", "parse_ms", "solve_ms", "error"];
+    let error = json_result(2021, 6, 2, &Err(Error::solve("No answer.").for_day(2021, 6)));
+    assert_eq!(json_keys(&error), keys);
+    assert_eq!(
+        error.dump(),
+        r#"{"year":2021,"day":6,"part":2,"status":"error","answer":null,"parse_ms":null,"solve_ms":null,"error":"2021 day 6: No answer."}"#
+    );
+    let timeout = json_result(2021, 6, 2, &Err(Error::timeout("Timed out after 1s.")));
+    assert_eq!(json_keys(&timeout), keys);
+    assert_eq!(timeout["status"], "timeout");
+    assert_eq!(timeout["error"], "Timed out after 1s.");
+}
@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::Error;
+
+/// Create the module for a new day from a template and register it,
+/// creating the year's module as well if it doesn't exist yet. The
+/// root is the directory containing Cargo.toml. Returns the paths of
+/// the files that were created or changed.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<String>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::usage("Day must be between 1 and 25."));
+    }
+    let src = root.join("src");
+    let lib_path = src.join("lib.rs");
+    if !lib_path.exists() {
+        return Err(Error::usage("New days must be created from the root of the repository."));
+    }
+    let year_dir = src.join(format!("year{}", year));
+    let day_path = year_dir.join(format!("day{}.rs", day));
+    if day_path.exists() {
+        return Err(Error::usage(format!("{} already exists.", day_path.display())));
+    }
+    let mod_path = year_dir.join("mod.rs");
+    let mut changed = Vec::<String>::new();
+
+    if mod_path.exists() {
+        let mod_rs = read(&mod_path)?;
+        write(&mod_path, &add_day(&mod_rs, day)?)?;
+    } else {
+        fs::create_dir_all(&year_dir)
+            .map_err(|e| Error::input(format!("Could not create {} ({}).", year_dir.display(), e)))?;
+        write(&mod_path, &year_module(year, day))?;
+        let lib_rs = read(&lib_path)?;
+        write(&lib_path, &add_year(&lib_rs, year)?)?;
+        changed.push(lib_path.display().to_string());
+    }
+    changed.push(mod_path.display().to_string());
+
+    write(&day_path, &day_module(day))?;
+    changed.push(day_path.display().to_string());
+    Ok(changed)
+}
+
+/// Source of a new day module. Day 25 only has a first part.
+fn day_module(day: u8) -> String {
+    let mut source = String::from(
+"use crate::input_file::{read_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
+
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    read_lines(input)
+}
+
+pub fn part1(_lines: &[String]) -> Result<Answer, Error> {
+    Err(Error::solve(\"Part 1 not yet implemented.\"))
+}
+");
+    if day != 25 {
+        source += "
+pub fn part2(_lines: &[String]) -> Result<Answer, Error> {
+    Err(Error::solve(\"Part 2 not yet implemented.\"))
+}
+";
+    }
+    source += "
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = \"\";
+
+    #[test]
+    #[ignore = \"example not filled in yet\"]
+    fn part1_example() {
+        let lines = parse(&PuzzleInput::text(EXAMPLE)).unwrap();
+        assert_eq!(part1(&lines).unwrap(), Answer::from(0));
+    }
+}
+";
+    source
+}
+
+/// Source of a new year module, registering its first day.
+fn year_module(year: u16, day: u8) -> String {
+    let list = if day == 25 { "part1_only" } else { "days" };
+    let mut source = format!("crate::solution::register_days!(\n    year: {},\n", year);
+    if day == 25 {
+        source += "    days: [],\n";
+    }
+    source += &format!("    {}: [\n        day{},\n    ],\n);\n", list, day);
+    source
+}
+
+/// Add the day to the register_days list in a year's mod.rs,
+/// keeping the days in order and laid out in rows as before.
+fn add_day(mod_rs: &str, day: u8) -> Result<String, Error> {
+    let invalid = || Error::parse("Year module does not have the expected register_days layout.");
+    let (start, end) = list_bounds(mod_rs, "days: [").ok_or_else(invalid)?;
+    let mut days = list_items(&mod_rs[start..end]);
+    let part1_only = list_bounds(mod_rs, "part1_only: [");
+    let part1_days = part1_only.map(|(s, e)| list_items(&mod_rs[s..e])).unwrap_or_default();
+    let name = format!("day{}", day);
+    if days.contains(&name) || part1_days.contains(&name) {
+        return Err(Error::usage(format!("Day {} is already registered.", day)));
+    }
+
+    if day == 25 {
+        return Ok(match part1_only {
+            Some((s, e)) => {
+                let mut part1_days = part1_days;
+                part1_days.push(name);
+                format!("{}{}{}", &mod_rs[..s], part1_days.join(", "), &mod_rs[e..])
+            },
+            None => {
+                let after_days = end + mod_rs[end..].find('\n').ok_or_else(invalid)? + 1;
+                format!("{}    part1_only: [{}],\n{}", &mod_rs[..after_days], name, &mod_rs[after_days..])
+            }
+        });
+    }
+
+    let existing = &mod_rs[start..end];
+    days.push(name);
+    days.sort_by_key(|d| d[3..].parse::<u8>().unwrap_or(0));
+    if !existing.contains('\n') {
+        return Ok(format!("{}{}{}", &mod_rs[..start], days.join(", "), &mod_rs[end..]));
+    }
+
+    // Keep the number of days per row, and whether they are padded to line up.
+    let first_row = existing.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+    let per_row = match list_items(first_row).len() {
+        0 => 7,
+        n => n
+    };
+    let padded = first_row.contains(",  ");
+    let width = if padded { days.iter().map(|d| d.len() + 1).max().unwrap() } else { 0 };
+
+    let mut list = String::from("\n");
+    for row in days.chunks(per_row) {
+        let cells: Vec<String> = row.iter()
+            .map(|d| format!("{:width$}", format!("{},", d), width = width))
+            .collect();
+        list += &format!("        {}\n", cells.join(" ").trim_end());
+    }
+    list += "    ";
+    Ok(format!("{}{}{}", &mod_rs[..start], list, &mod_rs[end..]))
+}
+
+/// Declare the year's module in lib.rs and add it to the registry.
+fn add_year(lib_rs: &str, year: u16) -> Result<String, Error> {
+    let invalid = || Error::parse("lib.rs does not declare the year modules as expected.");
+    let mut lines: Vec<String> = lib_rs.lines().map(|line| line.to_string()).collect();
+
+    let decls = lines.iter().position(|line| line.starts_with("pub mod year")).ok_or_else(invalid)?;
+    let mut years: Vec<String> = lines[decls]
+        .split(';')
+        .map(|decl| decl.trim().trim_start_matches("pub mod ").to_string())
+        .filter(|decl| !decl.is_empty())
+        .collect();
+    years.push(format!("year{}", year));
+    years.sort();
+    lines[decls] = years.iter().map(|y| format!("pub mod {};", y)).collect::<Vec<_>>().join(" ");
+
+    let register = format!("    year{}::register(&mut registry);", year);
+    let last = lines.iter()
+        .rposition(|line| line.trim_start().starts_with("year") && *line < register)
+        .or_else(|| lines.iter().position(|line| line.contains("Registry::new()")))
+        .ok_or_else(invalid)?;
+    lines.insert(last + 1, register);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Byte range of the contents of the list that follows the label.
+fn list_bounds(source: &str, label: &str) -> Option<(usize, usize)> {
+    let start = source.find(label)? + label.len();
+    let end = start + source[start..].find(']')?;
+    Some((start, end))
+}
+
+fn list_items(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map_err(|e| Error::input(format!("Could not read {} ({}).", path.display(), e)))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents)
+        .map_err(|e| Error::input(format!("Could not write {} ({}).", path.display(), e)))
+}
+
+#[test]
+fn add_day_keeps_layout() {
+    let mod_rs = "crate::solution::register_days!(
+    year: 2021,
+    days: [
+        day1,  day2,  day3,
+        day9,  day10,
+    ],
+);
+";
+    assert_eq!(add_day(mod_rs, 4).unwrap(), "crate::solution::register_days!(
+    year: 2021,
+    days: [
+        day1,  day2,  day3,
+        day4,  day9,  day10,
+    ],
+);
+");
+}
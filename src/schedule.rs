@@ -0,0 +1,270 @@
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::cancel::CancelToken;
+use crate::error::Error;
+use crate::input_file::PuzzleInput;
+use crate::solution::AnySolution;
+use crate::timing::Timing;
+
+thread_local! {
+    /// Whether a panic on this thread should be kept quiet and recorded
+    /// rather than printed, as it is on threads running a solution.
+    static QUIET_PANICS: Cell<bool> = const { Cell::new(false) };
+
+    /// What the last quiet panic on this thread said, and where.
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The answer to one part and how long it took, or why it failed.
+pub type PartResult = Result<(Answer, Timing), Error>;
+
+/// Some of the parts of one day, to be run against its input.
+pub struct Task {
+    pub solution: Arc<dyn AnySolution>,
+    pub input: PuzzleInput,
+    pub parts: Vec<u8>
+}
+
+/// How to run a list of tasks: how many to run at once, how long
+/// to wait for each part before giving up on it, and how long a part
+/// may take before it is reported as over budget.
+#[derive(Clone, Copy)]
+pub struct Schedule {
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub budget: Option<Duration>
+}
+
+impl Default for Schedule {
+    fn default() -> Schedule {
+        Schedule { jobs: 1, timeout: None, budget: None }
+    }
+}
+
+/// Run the tasks on up to the scheduled number of threads at once,
+/// calling report with the results of each task in the order the tasks
+/// were given, as soon as it and every task before it have finished.
+/// Each task is run as run_task does, so one that panics or runs past
+/// the timeout fails with an error instead of holding up the rest.
+pub fn run_tasks<F: FnMut(&Task, Vec<PartResult>)>(tasks: Vec<Task>, schedule: &Schedule, mut report: F) {
+    let tasks: Arc<Vec<Arc<Task>>> = Arc::new(tasks.into_iter().map(Arc::new).collect());
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..schedule.jobs.clamp(1, tasks.len().max(1)) {
+        let (tasks, next, sender) = (Arc::clone(&tasks), Arc::clone(&next), sender.clone());
+        let timeout = schedule.timeout;
+        thread::spawn(move || loop {
+            let i = next.fetch_add(1, Ordering::SeqCst);
+            if i >= tasks.len() || sender.send((i, run_task(&tasks[i], timeout))).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    // Hold on to results that come in early until their turn.
+    let mut finished = BTreeMap::new();
+    let mut reported = 0;
+    for (i, results) in receiver {
+        finished.insert(i, results);
+        while let Some(results) = finished.remove(&reported) {
+            report(&tasks[reported], results);
+            reported += 1;
+        }
+    }
+}
+
+/// Run the task on a thread of its own, watching over it from this one.
+/// If a part takes longer than the timeout, it is cancelled, and it and
+/// any parts after it fail with a timeout error. Threads can't be
+/// stopped from outside, so a cancelled part carries on in the background
+/// until it next checks for cancellation, finishes or the program exits.
+/// If the solution panics, the parts that didn't finish fail instead,
+/// with what the panic said in place of the usual message on stderr.
+pub fn run_task(task: &Arc<Task>, timeout: Option<Duration>) -> Vec<PartResult> {
+    install_panic_hook();
+    let (sender, receiver) = mpsc::channel();
+    let token = CancelToken::new();
+    let panic_message = Arc::new(Mutex::new(None));
+    let (shared, shared_token, shared_message) = (Arc::clone(task), token.clone(), Arc::clone(&panic_message));
+    thread::spawn(move || {
+        shared_token.install();
+        QUIET_PANICS.with(|quiet| quiet.set(true));
+        let run = panic::catch_unwind(AssertUnwindSafe(|| {
+            shared.solution.run_parts_with(&shared.parts, &shared.input, &mut |result| {
+                let _ = sender.send(result);
+            });
+        }));
+        if run.is_err() {
+            // Record the message before the sender is dropped, so it's
+            // there by the time the watcher sees the channel close.
+            *shared_message.lock().unwrap() = PANIC_MESSAGE.with(|message| message.borrow_mut().take());
+        }
+        drop(sender);
+    });
+
+    let mut results = Vec::new();
+    while results.len() < task.parts.len() {
+        let received = match timeout {
+            Some(timeout) => receiver.recv_timeout(timeout),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        };
+        let error = match received {
+            Ok(result) => {
+                results.push(result);
+                continue;
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                token.cancel();
+                Error::timeout(format!("Timed out after {}s.", timeout.unwrap_or_default().as_secs_f64()))
+            },
+            Err(RecvTimeoutError::Disconnected) => match panic_message.lock().unwrap().take() {
+                Some(message) => Error::solve(format!("The solution panicked: {}", message)),
+                None => Error::solve("The solution panicked.")
+            }
+        };
+        results.resize(task.parts.len(), Err(error.for_day(task.solution.year(), task.solution.day())));
+    }
+    results
+}
+
+/// Replace the panic hook, once, with one that records panics on threads
+/// running a solution for run_task to report, and leaves the rest to the
+/// hook it replaces.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if QUIET_PANICS.with(Cell::get) {
+                PANIC_MESSAGE.with(|message| *message.borrow_mut() = Some(describe_panic(info)));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// What a panic said and where, as the default hook would print it.
+fn describe_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let said = payload.downcast_ref::<&str>().copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    match info.location() {
+        Some(location) => format!("{} at {}", said, location),
+        None => said.to_string()
+    }
+}
+
+#[test]
+fn reports_in_order_and_times_out_slow_tasks() {
+    use crate::solution::Solution;
+
+    use crate::error::ErrorKind;
+
+    // A day whose parts take as many milliseconds as its input says.
+    struct Sleep(u8);
+
+    fn nap(millis: u64) -> Result<Answer, Error> {
+        for _ in 0..millis / 10 {
+            crate::cancel::check()?;
+            thread::sleep(Duration::from_millis(10));
+        }
+        Ok(Answer::from(millis))
+    }
+
+    impl Solution for Sleep {
+        type Input = Vec<u64>;
+
+        fn year(&self) -> u16 {
+            2000
+        }
+
+        fn day(&self) -> u8 {
+            self.0
+        }
+
+        fn parse(&self, input: &PuzzleInput) -> Result<Vec<u64>, Error> {
+            crate::input_file::read_all_to_string(input)?
+                .split(' ')
+                .map(|millis| Ok(millis.parse()?))
+                .collect()
+        }
+
+        fn part1(&self, millis: &Vec<u64>) -> Result<Answer, Error> {
+            nap(millis[0])
+        }
+
+        fn part2(&self, millis: &Vec<u64>) -> Result<Answer, Error> {
+            nap(millis[1])
+        }
+    }
+
+    let tasks = ["300 0", "5000 0", "10 5000", "100 20"].iter().enumerate()
+        .map(|(i, millis)| Task {
+            solution: Arc::new(Sleep(i as u8 + 1)),
+            input: PuzzleInput::text(*millis),
+            parts: vec![1, 2]
+        })
+        .collect();
+    let schedule = Schedule { jobs: 4, timeout: Some(Duration::from_millis(1000)), budget: None };
+    let mut reported = Vec::<(u8, Vec<Result<Answer, ErrorKind>>)>::new();
+    run_tasks(tasks, &schedule, |task, results| {
+        let answers = results.into_iter().map(|r| r.map(|(a, _)| a).map_err(|e| e.kind)).collect();
+        reported.push((task.solution.day(), answers));
+    });
+
+    let ms = |millis: u64| Ok(Answer::from(millis));
+    assert_eq!(reported, vec![
+        (1, vec![ms(300), ms(0)]),
+        (2, vec![Err(ErrorKind::Timeout), Err(ErrorKind::Timeout)]),
+        (3, vec![ms(10), Err(ErrorKind::Timeout)]),
+        (4, vec![ms(100), ms(20)])
+    ]);
+}
+
+#[test]
+fn reports_what_a_panicking_solution_said() {
+    use crate::solution::Solution;
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = String;
+
+        fn year(&self) -> u16 {
+            2000
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn parse(&self, input: &PuzzleInput) -> Result<String, Error> {
+            crate::input_file::read_all_to_string(input)
+        }
+
+        fn part1(&self, _: &String) -> Result<Answer, Error> {
+            Ok(Answer::from(1u64))
+        }
+
+        fn part2(&self, input: &String) -> Result<Answer, Error> {
+            panic!("Can't cope with {}.", input)
+        }
+    }
+
+    let task = Arc::new(Task { solution: Arc::new(Panics), input: PuzzleInput::text("this"), parts: vec![1, 2] });
+    let results = run_task(&task, None);
+    assert_eq!(results[0].as_ref().map(|(answer, _)| answer.clone()).ok(), Some(Answer::from(1u64)));
+    let message = results[1].as_ref().err().map(|error| error.message.clone()).unwrap_or_default();
+    assert!(message.starts_with("The solution panicked: Can't cope with this. at src/schedule.rs:"), "{}", message);
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::cancel;
+use crate::data_structs::MinPriorityQueue;
+use crate::error::Error;
+
+/// The best way found from the start to a goal: what it
+/// cost, and every node along it, from the start to the goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>
+}
+
+impl<N, C> Path<N, C> {
+    /// The node the path ends at.
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("A path always has a start.")
+    }
+}
+
+/// Find the path from start to a goal with the fewest steps,
+/// where neighbours gives the nodes one step away from a node.
+/// Fails if cancelled, as searches can take a long time.
+pub fn bfs<N, FN, IN, FG>(
+    start: N,
+    mut neighbours: FN,
+    mut is_goal: FG
+) -> Result<Option<Path<N, usize>>, Error>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool
+{
+    let mut seen = Seen::new(start, 0);
+    let mut queue = VecDeque::<usize>::from(vec![0]);
+    while let Some(current) = queue.pop_front() {
+        cancel::check()?;
+        let (node, steps) = (seen.nodes[current].node.clone(), seen.nodes[current].cost);
+        if is_goal(&node) {
+            return Ok(Some(seen.path_to(current)));
+        }
+        for next in neighbours(&node) {
+            if !seen.index.contains_key(&next) {
+                queue.push_back(seen.add(next, steps + 1, current));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Find the cheapest path from start to a goal, where neighbours
+/// gives the nodes one step away from a node along with the cost
+/// of stepping to each. Costs must not be negative.
+pub fn dijkstra<N, C, FN, IN, FG>(
+    start: N,
+    neighbours: FN,
+    is_goal: FG
+) -> Result<Option<Path<N, C>>, Error>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Find the cheapest path from start to a goal as dijkstra does,
+/// trying first the nodes that the heuristic guesses are closest to
+/// a goal. The guess must never be more than the real cost to the
+/// nearest goal, or a path that isn't the cheapest may be found.
+/// Nodes are tried again if a cheaper way to them turns up later,
+/// so the guess needn't be consistent from one node to the next,
+/// though the search is quickest when it is.
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut neighbours: FN,
+    mut heuristic: FH,
+    mut is_goal: FG
+) -> Result<Option<Path<N, C>>, Error>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool
+{
+    let mut queue = MinPriorityQueue::<usize, C>::new();
+    queue.push(0, heuristic(&start));
+    let mut seen = Seen::new(start, C::default());
+    while let Some((current, _)) = queue.pop() {
+        cancel::check()?;
+        let (node, cost) = (seen.nodes[current].node.clone(), seen.nodes[current].cost);
+        if is_goal(&node) {
+            return Ok(Some(seen.path_to(current)));
+        }
+        for (next, step_cost) in neighbours(&node) {
+            let next_cost = cost + step_cost;
+            let priority = next_cost + heuristic(&next);
+            match seen.index.get(&next) {
+                None => queue.push(seen.add(next, next_cost, current), priority),
+                Some(&i) if next_cost < seen.nodes[i].cost => {
+                    seen.nodes[i].cost = next_cost;
+                    seen.nodes[i].parent = Some(current);
+                    queue.push_decrease(i, priority);
+                },
+                Some(_) => ()
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// A node reached during a search, the cheapest way
+/// found to it so far and where that way came from.
+struct Reached<N, C> {
+    node: N,
+    cost: C,
+    parent: Option<usize>
+}
+
+/// Every node reached during a search, numbered in the order
+/// they were reached, so the queue only has to hold numbers.
+struct Seen<N, C> {
+    nodes: Vec<Reached<N, C>>,
+    index: HashMap<N, usize>
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Seen<N, C> {
+    fn new(start: N, cost: C) -> Seen<N, C> {
+        let mut index = HashMap::new();
+        index.insert(start.clone(), 0);
+        Seen { nodes: vec![Reached { node: start, cost, parent: None }], index }
+    }
+
+    fn add(&mut self, node: N, cost: C, parent: usize) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(Reached { node, cost, parent: Some(parent) });
+        i
+    }
+
+    fn path_to(&self, end: usize) -> Path<N, C> {
+        let mut nodes = vec![self.nodes[end].node.clone()];
+        let mut current = end;
+        while let Some(parent) = self.nodes[current].parent {
+            nodes.push(self.nodes[parent].node.clone());
+            current = parent;
+        }
+        nodes.reverse();
+        Path { cost: self.nodes[end].cost, nodes }
+    }
+}
+
+#[test]
+fn searches_find_shortest_and_cheapest_paths() {
+    use crate::data_structs::{Coord, Grid, manhattan_dist};
+
+    // Walls are #, and stepping onto a cell costs its digit.
+    let grid = Grid::from_str("1193\n1#91\n1#91\n1111", |c| Ok(c.to_digit(10).map(|d| d as u8))).unwrap();
+    let (start, goal) = (Coord { x: 0, y: 0 }, Coord { x: 3, y: 0 });
+    let open = |c: &Coord| grid.neighbours4(c.x, c.y)
+        .filter_map(|(next, cost)| cost.map(|cost| (next, cost as u32)))
+        .collect::<Vec<_>>();
+
+    let fewest = bfs(start.clone(), |c| open(c).into_iter().map(|(next, _)| next), |c| *c == goal).unwrap().unwrap();
+    assert_eq!((fewest.cost, fewest.goal()), (3, &goal));
+    let cheapest = dijkstra(start.clone(), open, |c| *c == goal).unwrap().unwrap();
+    assert_eq!(cheapest.cost, 11);
+    assert_eq!(cheapest.nodes.len(), 10);
+    let guided = astar(start.clone(), open, |c| manhattan_dist(c, &goal) as u32, |c| *c == goal).unwrap().unwrap();
+    assert_eq!(guided, cheapest);
+
+    // An explicit graph where the goal can't be reached.
+    let graph = HashMap::from([("a", vec![("b", 1)]), ("b", vec![("a", 1)]), ("c", vec![])]);
+    assert_eq!(dijkstra("a", |n| graph[n].clone(), |n| *n == "c").unwrap(), None);
+
+    // The guess for b is never too high, but makes a look closer than it
+    // is, so a is first reached the dear way and has to be tried again.
+    let graph = HashMap::from([
+        ("s", vec![("a", 4), ("b", 1)]), ("b", vec![("a", 1)]), ("a", vec![("g", 5)]), ("g", vec![])
+    ]);
+    let guess = |n: &&str| if *n == "b" { 6 } else { 0 };
+    let path = astar("s", |n| graph[n].clone(), guess, |n| *n == "g").unwrap().unwrap();
+    assert_eq!((path.cost, path.nodes), (7, vec!["s", "b", "a", "g"]));
+}
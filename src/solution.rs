@@ -0,0 +1,283 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::answer::Answer;
+use crate::cancel;
+use crate::error::Error;
+use crate::input_file::PuzzleInput;
+use crate::timing::{time, Timing};
+
+/// A solution to a single day's puzzle. Parsing the input is
+/// kept separate from solving each part.
+pub trait Solution {
+    type Input;
+
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    /// Read and parse the puzzle input.
+    fn parse(&self, input: &PuzzleInput) -> Result<Self::Input, Error>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, Error>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, Error>;
+
+    /// Whether there is a second part to this puzzle
+    /// (day 25 only ever has one).
+    fn has_part2(&self) -> bool {
+        true
+    }
+}
+
+/// Object safe view of a Solution, so that solutions with
+/// different input types can be stored in the same registry
+/// and shared between threads.
+pub trait AnySolution: Send + Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn has_part(&self, part: u8) -> bool;
+
+    /// The parts this puzzle has, in order.
+    fn parts(&self) -> Vec<u8> {
+        (1..=2).filter(|part| self.has_part(*part)).collect()
+    }
+
+    /// Parse the puzzle input once and run each of the given parts on
+    /// it, measuring how long parsing and solving each took, and passing
+    /// the result of each part to report as soon as it's done. If parsing
+    /// fails, every part fails with the same error, and if the work is
+    /// cancelled, the remaining parts aren't run. Any error is tagged
+    /// with the year and day.
+    fn run_parts_with(
+        &self,
+        parts: &[u8],
+        input: &PuzzleInput,
+        report: &mut dyn FnMut(Result<(Answer, Timing), Error>)
+    );
+
+    /// Run each of the given parts as run_parts_with does,
+    /// collecting the results.
+    fn run_parts_timed(&self, parts: &[u8], input: &PuzzleInput) -> Vec<Result<(Answer, Timing), Error>> {
+        let mut results = Vec::new();
+        self.run_parts_with(parts, input, &mut |result| results.push(result));
+        results
+    }
+
+    /// Parse the puzzle input and run the given part on it.
+    fn run_timed(&self, part: u8, input: &PuzzleInput) -> Result<(Answer, Timing), Error> {
+        self.run_parts_timed(&[part], input).remove(0)
+    }
+}
+
+impl<S: Solution + Send + Sync> AnySolution for S {
+    fn year(&self) -> u16 {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn has_part(&self, part: u8) -> bool {
+        match part {
+            1 => true,
+            2 => self.has_part2(),
+            _ => false
+        }
+    }
+
+    fn run_parts_with(
+        &self,
+        parts: &[u8],
+        input: &PuzzleInput,
+        report: &mut dyn FnMut(Result<(Answer, Timing), Error>)
+    ) {
+        let for_day = |e: Error| e.for_day(Solution::year(self), Solution::day(self));
+        let (parsed, parse) = time(|| self.parse(input));
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => return parts.iter().for_each(|_| report(Err(for_day(e.clone()))))
+        };
+        for part in parts {
+            if let Err(e) = cancel::check() {
+                return report(Err(for_day(e)));
+            }
+            let (answer, solve) = time(|| match part {
+                1 => self.part1(&parsed),
+                2 => self.part2(&parsed),
+                _ => Err(Error::usage("Part must be 1 or 2."))
+            });
+            report(answer.map(|answer| (answer, Timing { parse, solve })).map_err(for_day));
+        }
+    }
+}
+
+/// A function solving one part of a day's puzzle.
+type PartFn<U> = fn(&U) -> Result<Answer, Error>;
+
+/// A Solution made up of the parse, part1 and part2
+/// functions from a day module. The part functions can take
+/// the parsed input by any type it borrows as, e.g. a slice
+/// rather than a Vec.
+pub struct DaySolution<T: Borrow<U>, U: ?Sized> {
+    year: u16,
+    day: u8,
+    parse: fn(&PuzzleInput) -> Result<T, Error>,
+    part1: PartFn<U>,
+    part2: Option<PartFn<U>>
+}
+
+impl<T: Borrow<U>, U: ?Sized> DaySolution<T, U> {
+    pub fn new(
+        year: u16,
+        day: u8,
+        parse: fn(&PuzzleInput) -> Result<T, Error>,
+        part1: PartFn<U>,
+        part2: Option<PartFn<U>>
+    ) -> DaySolution<T, U> {
+        DaySolution { year, day, parse, part1, part2 }
+    }
+}
+
+impl<T: Borrow<U>, U: ?Sized> Solution for DaySolution<T, U> {
+    type Input = T;
+
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn parse(&self, input: &PuzzleInput) -> Result<T, Error> {
+        (self.parse)(input)
+    }
+
+    fn part1(&self, input: &T) -> Result<Answer, Error> {
+        (self.part1)(input.borrow())
+    }
+
+    fn part2(&self, input: &T) -> Result<Answer, Error> {
+        match self.part2 {
+            Some(part2) => part2(input.borrow()),
+            None => Err(Error::usage("There is no part 2."))
+        }
+    }
+
+    fn has_part2(&self) -> bool {
+        self.part2.is_some()
+    }
+}
+
+/// All of the solutions that have been implemented,
+/// ordered by year and day.
+pub struct Registry {
+    solutions: BTreeMap<(u16, u8), Arc<dyn AnySolution>>
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { solutions: BTreeMap::new() }
+    }
+
+    /// Add a solution to the registry. Panics if a solution
+    /// for the same year and day has already been added.
+    pub fn add<S: Solution + Send + Sync + 'static>(&mut self, solution: S) {
+        let key = (Solution::year(&solution), Solution::day(&solution));
+        if self.solutions.contains_key(&key) {
+            panic!("Solution to {} day {} registered twice.", key.0, key.1);
+        }
+        self.solutions.insert(key, Arc::new(solution));
+    }
+
+    /// Look up the solution for the given year and day.
+    pub fn get(&self, year: u16, day: u8) -> Option<&dyn AnySolution> {
+        self.solutions.get(&(year, day)).map(|s| s.as_ref())
+    }
+
+    /// Look up the solution for the given year and day, to be
+    /// run on another thread.
+    pub fn shared(&self, year: u16, day: u8) -> Option<Arc<dyn AnySolution>> {
+        self.solutions.get(&(year, day)).cloned()
+    }
+
+    /// All years that have at least one solution.
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.solutions.keys().map(|(year, _)| *year).collect();
+        years.dedup();
+        years
+    }
+
+    /// All days with a solution in the given year.
+    pub fn days(&self, year: u16) -> Vec<u8> {
+        self.solutions.keys()
+            .filter(|(y, _)| *y == year)
+            .map(|(_, day)| *day)
+            .collect()
+    }
+
+    /// Iterate over every solution in year and day order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn AnySolution> {
+        self.solutions.values().map(|s| s.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        Registry::new()
+    }
+}
+
+/// Get the day number from the name of a day module, e.g. "day14".
+pub fn day_from_module_name(module_name: &str) -> u8 {
+    module_name
+        .strip_prefix("day")
+        .and_then(|day| day.parse().ok())
+        .unwrap_or_else(|| panic!("Day module {} is not named dayN.", module_name))
+}
+
+/// Declare the day modules for a year and generate a `register`
+/// function that adds each of them to the registry. Every day module
+/// needs `parse`, `part1` and `part2` functions, returning a `Result` of
+/// the parsed input and an `Answer` respectively, apart from those listed
+/// under `part1_only`, which have no second part.
+macro_rules! register_days {
+    (
+        year: $year:literal,
+        days: [$($day:ident),* $(,)?]
+        $(, part1_only: [$($part1_day:ident),* $(,)?])?
+        $(,)?
+    ) => {
+        $(pub mod $day;)*
+        $($(pub mod $part1_day;)*)?
+
+        /// Add every implemented day for this year to the registry.
+        pub fn register(registry: &mut crate::solution::Registry) {
+            $(
+                registry.add(crate::solution::DaySolution::new(
+                    $year,
+                    crate::solution::day_from_module_name(stringify!($day)),
+                    $day::parse,
+                    $day::part1,
+                    Some($day::part2)
+                ));
+            )*
+            $($(
+                registry.add(crate::solution::DaySolution::new(
+                    $year,
+                    crate::solution::day_from_module_name(stringify!($part1_day)),
+                    $part1_day::parse,
+                    $part1_day::part1,
+                    None
+                ));
+            )*)?
+        }
+    };
+}
+
+pub(crate) use register_days;
@@ -0,0 +1,232 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use json::JsonValue;
+
+use crate::error::Error;
+use crate::http;
+
+/// Default location of the history of submitted answers.
+pub const HISTORY_FILE: &str = "submissions.json";
+
+/// Wait at least this long after a wrong answer before submitting
+/// again, as the puzzle site refuses answers sent sooner.
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited => "rate limited",
+            Verdict::AlreadySolved => "already solved"
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        [
+            Verdict::Correct, Verdict::Wrong, Verdict::TooHigh,
+            Verdict::TooLow, Verdict::RateLimited, Verdict::AlreadySolved
+        ].iter().copied().find(|v| v.name() == name)
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+/// One answer that was submitted, and when.
+#[derive(Clone, Debug)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub time: u64
+}
+
+/// Every answer submitted so far, stored as a JSON array of attempts.
+pub struct History {
+    attempts: Vec<Attempt>
+}
+
+impl History {
+    /// Load the history from the file at path. A file
+    /// that doesn't exist is treated as an empty history.
+    pub fn load(path: &str) -> Result<History, Error> {
+        if !Path::new(path).exists() {
+            return Ok(History { attempts: Vec::new() });
+        }
+        let invalid = || Error::input(format!("History file {} is not valid.", path));
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::input(format!("Could not read {} ({}).", path, e)))?;
+        let json = json::parse(&contents).map_err(|_| invalid())?;
+        let mut attempts = Vec::<Attempt>::new();
+        for attempt in json.members() {
+            attempts.push(Attempt {
+                year: attempt["year"].as_u16().ok_or_else(invalid)?,
+                day: attempt["day"].as_u8().ok_or_else(invalid)?,
+                part: attempt["part"].as_u8().ok_or_else(invalid)?,
+                answer: attempt["answer"].as_str().ok_or_else(invalid)?.to_string(),
+                verdict: attempt["verdict"].as_str().and_then(Verdict::from_name).ok_or_else(invalid)?,
+                time: attempt["time"].as_u64().ok_or_else(invalid)?
+            });
+        }
+        Ok(History { attempts })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let mut json = JsonValue::new_array();
+        for attempt in &self.attempts {
+            let mut entry = JsonValue::new_object();
+            entry["year"] = attempt.year.into();
+            entry["day"] = attempt.day.into();
+            entry["part"] = attempt.part.into();
+            entry["answer"] = attempt.answer.as_str().into();
+            entry["verdict"] = attempt.verdict.name().into();
+            entry["time"] = attempt.time.into();
+            json.push(entry).unwrap();
+        }
+        fs::write(path, json.pretty(2))
+            .map_err(|e| Error::input(format!("Could not write {} ({}).", path, e)))
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Why the answer shouldn't be submitted at the given time, if
+    /// it has already been solved, the answer is known to be wrong or
+    /// out of bounds, or it's too soon after the last wrong answer.
+    pub fn refusal(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let attempts: Vec<&Attempt> = self.attempts.iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part)
+            .collect();
+        if let Some(solved) = attempts.iter().find(|a| a.verdict == Verdict::Correct) {
+            return Some(format!("Already solved with {}.", solved.answer));
+        }
+        if let Some(wrong) = attempts.iter().find(|a| a.answer == answer && a.verdict.is_wrong()) {
+            return Some(format!("{} was already submitted and was {}.", answer, wrong.verdict.name()));
+        }
+        if let Ok(n) = answer.parse::<i64>() {
+            let bound = |verdict: Verdict| attempts.iter()
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i64>().ok());
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| n >= *high) {
+                return Some(format!("{} is too high, as {} was.", n, high));
+            }
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|low| n <= *low) {
+                return Some(format!("{} is too low, as {} was.", n, low));
+            }
+        }
+        let last_wrong = self.attempts.iter()
+            .filter(|a| a.verdict.is_wrong() || a.verdict == Verdict::RateLimited)
+            .map(|a| a.time)
+            .max();
+        match last_wrong {
+            Some(time) if now < time + MIN_INTERVAL_SECS => Some(format!(
+                "The last answer was wrong; wait {}s before submitting again.",
+                time + MIN_INTERVAL_SECS - now
+            )),
+            _ => None
+        }
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Post the answer to BASE_URL/YEAR/day/N/answer as the logged in
+/// user, as on the Advent of Code site, and interpret the response.
+pub fn submit(base_url: &str, session: &str, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+    let cookie = format!("session={}", session);
+    let body = format!("level={}&answer={}", part, form_encode(answer));
+    let response = http::request(
+        base_url,
+        "POST",
+        &format!("/{}/day/{}/answer", year, day),
+        &[("Cookie", &cookie), ("Content-Type", "application/x-www-form-urlencoded")],
+        Some(&body)
+    )?;
+    if response.status != 200 {
+        return Err(Error::input(format!("Submitting failed with status {}.", response.status)));
+    }
+    parse_verdict(&response.body)
+        .ok_or_else(|| Error::input("Could not understand the response to the answer."))
+}
+
+fn parse_verdict(body: &str) -> Option<Verdict> {
+    if body.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if body.contains("You gave an answer too recently") {
+        Some(Verdict::RateLimited)
+    } else if body.contains("You don't seem to be solving the right level") {
+        Some(Verdict::AlreadySolved)
+    } else if body.contains("your answer is too high") {
+        Some(Verdict::TooHigh)
+    } else if body.contains("your answer is too low") {
+        Some(Verdict::TooLow)
+    } else if body.contains("That's not the right answer") {
+        Some(Verdict::Wrong)
+    } else {
+        None
+    }
+}
+
+fn form_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            b' ' => "+".to_string(),
+            _ => format!("%{:02X}", b)
+        })
+        .collect()
+}
+
+#[test]
+fn refuses_known_wrong_and_out_of_bounds_answers() {
+    let attempt = |answer: &str, verdict: Verdict, time: u64| Attempt {
+        year: 2021, day: 1, part: 1, answer: answer.to_string(), verdict, time
+    };
+    let mut history = History { attempts: Vec::new() };
+    history.record(attempt("500", Verdict::TooHigh, 1000));
+    history.record(attempt("100", Verdict::TooLow, 1100));
+    history.record(attempt("250", Verdict::Wrong, 1200));
+
+    assert!(history.refusal(2021, 1, 1, "250", 5000).is_some());
+    assert!(history.refusal(2021, 1, 1, "600", 5000).is_some());
+    assert!(history.refusal(2021, 1, 1, "50", 5000).is_some());
+    assert!(history.refusal(2021, 1, 1, "300", 1230).is_some());
+    assert!(history.refusal(2021, 1, 1, "300", 5000).is_none());
+    assert!(history.refusal(2021, 1, 2, "600", 5000).is_none());
+}
+
+#[test]
+fn submit_parses_response_from_server() {
+    let (url, server) = crate::http::serve_once(
+        "HTTP/1.0 200 OK\r\n\r\n<p>That's not the right answer; your answer is too high.</p>"
+    );
+    let verdict = submit(&url, "abc123", 2021, 1, 2, "1 2").unwrap();
+    assert_eq!(verdict, Verdict::TooHigh);
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /2021/day/1/answer HTTP/1.0"));
+    assert!(request.ends_with("level=2&answer=1+2"));
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+/// How long it took to read and parse the input,
+/// and then how long it took to solve the part.
+#[derive(Clone, Copy)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration
+}
+
+/// Run f and measure how long it takes.
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Summary of the durations over several runs.
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+    /// Panics if there are no durations.
+    pub fn from_durations(durations: &[Duration]) -> Stats {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        Stats {
+            min: *sorted.first().expect("No durations to summarise."),
+            median: sorted[sorted.len() / 2],
+            max: *sorted.last().unwrap()
+        }
+    }
+}
+
+/// Format a duration in milliseconds, so that durations
+/// line up when printed in a column.
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", millis(duration))
+}
+
+/// A duration as a number of milliseconds.
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
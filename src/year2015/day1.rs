@@ -1,39 +1,43 @@
-use crate::input_file::read_all_to_string;
+use crate::input_file::{read_all_to_string, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
+
+pub fn parse(input: &PuzzleInput) -> Result<String, Error> {
+    read_all_to_string(input)
+}
 
 /// Print the floor that Santa ends up on when following
-/// the instructions in the file at input_file_path.
+/// the instructions in the input.
 /// ( => go up a floor.
 /// ) => go down a floor.
-pub fn part1(input_file_path: &str) {
-    let input: String = read_all_to_string(input_file_path);
+pub fn part1(input: &str) -> Result<Answer, Error> {
     let mut floor: i32 = 0;
     for paren in input.chars() {
         match paren {
             '(' => floor += 1,
             ')' => floor -= 1,
-            _ => panic!("Invalid character in file.")
+            _ => return Err(Error::parse(format!("Invalid character {:?} in input.", paren)))
         }
     }
-    println!("{}", floor);
+    Ok(Answer::from(floor))
 }
 
 /// Print the index of the command that first takes
 /// Santa underground when following
-/// the instructions in the file at input_file_path.
+/// the instructions in the input.
 /// ( => go up a floor.
 /// ) => go down a floor.
-pub fn part2(input_file_path: &str) {
-    let input: String = read_all_to_string(input_file_path);
+pub fn part2(input: &str) -> Result<Answer, Error> {
     let mut floor: i32 = 0;
     for (i, paren) in input.chars().enumerate() {
         match paren {
             '(' => floor += 1,
             ')' => floor -= 1,
-            _ => panic!("Invalid character in file.")
+            _ => return Err(Error::parse(format!("Invalid character {:?} in input.", paren)))
         }
         if floor < 0 {
-            println!("{}", i+1);
-            break;
+            return Ok(Answer::from(i+1));
         }
     }
+    Err(Error::solve("Never entered the basement."))
 }
\ No newline at end of file
@@ -1,18 +1,21 @@
 use json::JsonValue;
-use crate::input_file::read_all_to_string;
+use crate::input_file::{read_all_to_string, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let s = read_all_to_string(input_file_path);
-    let j = json::parse(&s).expect("Json failed to parse.");
-    let n = count_numbers(&j, false);
-    println!("{}", n);
+pub fn parse(input: &PuzzleInput) -> Result<JsonValue, Error> {
+    let s = read_all_to_string(input)?;
+    json::parse(&s).map_err(|e| Error::parse(format!("Json failed to parse ({}).", e)))
 }
 
-pub fn part2(input_file_path: &str) {
-    let s = read_all_to_string(input_file_path);
-    let j = json::parse(&s).expect("Json failed to parse.");
-    let n = count_numbers(&j, true);
-    println!("{}", n);
+pub fn part1(j: &JsonValue) -> Result<Answer, Error> {
+    let n = count_numbers(j, false);
+    Ok(Answer::from(n))
+}
+
+pub fn part2(j: &JsonValue) -> Result<Answer, Error> {
+    let n = count_numbers(j, true);
+    Ok(Answer::from(n))
 }
 
 fn count_numbers(j: &json::JsonValue, ignore_red: bool) -> i32 {
@@ -2,18 +2,26 @@ use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use lazy_static::lazy_static;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::captures::{named, named_str, parse_captures};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (guests, happiness) = parse_lines(&lines);
-    let (_, total_happiness) = find_optimal_seating(vec![], guests, &happiness);
-    println!("{}", total_happiness);
+/// The guests, and the happiness each gains from sitting next to each other.
+type Guests = (Vec<String>, HashMap<(String, String), i32>);
+
+pub fn parse(input: &PuzzleInput) -> Result<Guests, Error> {
+    collect_guests(parse_each_line(input, parse_line)?)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (mut guests, mut happiness) = parse_lines(&lines);
+pub fn part1((guests, happiness): &Guests) -> Result<Answer, Error> {
+    let (_, total_happiness) = find_optimal_seating(vec![], guests.clone(), happiness);
+    Ok(Answer::from(total_happiness))
+}
+
+pub fn part2((guests, happiness): &Guests) -> Result<Answer, Error> {
+    let mut guests = guests.clone();
+    let mut happiness = happiness.clone();
     // Add myself
     for guest in &guests {
         happiness.insert((guest.to_owned(), "Rob".to_owned()), 0);
@@ -22,7 +30,7 @@ pub fn part2(input_file_path: &str) {
     guests.push("Rob".to_owned());
     
     let (_, total_happiness) = find_optimal_seating(vec![], guests, &happiness);
-    println!("{}", total_happiness);
+    Ok(Answer::from(total_happiness))
 }
 
 lazy_static! {
@@ -32,27 +40,39 @@ lazy_static! {
         ).unwrap();
 }
 
-fn parse_lines(lines: &Vec<String>) -> (Vec<String>, HashMap<(String, String), i32>) {
+/// One guest, the guest they would sit next to,
+/// and how much happier that would make them.
+fn parse_line(line: &str) -> Result<(String, String, i32), Error> {
+    parse_captures(&LINE_RE, line, |caps| {
+        let units: i32 = named(caps, "units")?;
+        let signed_units = match named_str(caps, "gainorlose")? {
+            "gain" => units,
+            _ => -units
+        };
+        Ok((named_str(caps, "name1")?.to_owned(), named_str(caps, "name2")?.to_owned(), signed_units))
+    })
+}
+
+fn collect_guests(preferences: Vec<(String, String, i32)>) -> Result<Guests, Error> {
     let mut happiness = HashMap::<(String, String), i32>::new();
     let mut guests = HashSet::<String>::new();
-    for line in lines {
-        let caps = LINE_RE.captures(&line)
-            .expect(&format!("Input line did not match expected pattern. {}", line));
-        let name1 = caps.name("name1").unwrap().as_str().to_owned();
+    for (name1, name2, signed_units) in preferences {
         guests.insert(name1.clone());
-        let name2 = caps.name("name2").unwrap().as_str().to_owned();
-        let units = caps.name("units").unwrap().as_str();
-        let units = units.parse::<i32>().expect("Could not parse amount of happiness units");
-        let gain_or_lose = caps.name("gainorlose").unwrap().as_str();
-        let signed_units;
-        match gain_or_lose {
-            "gain" => signed_units = units,
-            "lose" => signed_units = -units,
-            _ => panic!("Invalid syntax of line - 'gain' or 'lose' expected after 'would'.")
-        }
+        guests.insert(name2.clone());
         happiness.insert((name1, name2), signed_units);
     }
-    (guests.into_iter().collect(), happiness)
+    if guests.len() < 2 {
+        return Err(Error::parse("Expected at least two guests."));
+    }
+    // Every guest needs to say how they feel about every other
+    for name1 in &guests {
+        for name2 in &guests {
+            if name1 != name2 && !happiness.contains_key(&(name1.clone(), name2.clone())) {
+                return Err(Error::parse(format!("Missing how {} feels about sitting next to {}.", name1, name2)));
+            }
+        }
+    }
+    Ok((guests.into_iter().collect(), happiness))
 }
 
 fn find_optimal_seating(
@@ -1,40 +1,24 @@
-use regex::Regex;
-use crate::input_file::read_lines;
-use lazy_static::lazy_static;
+use crate::input_file::{extract_ints, parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let reindeer= parse_lines(&lines);
-    let furthest_distance = simulate_sprint_race(reindeer);
-    println!("{}", furthest_distance);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Reindeer>, Error> {
+    parse_each_line(input, parse_reindeer)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let reindeer= parse_lines(&lines);
-    let winning_score = simulate_points_race(reindeer);
-    println!("{}", winning_score);
+pub fn part1(reindeer: &[Reindeer]) -> Result<Answer, Error> {
+    let furthest_distance = simulate_sprint_race(reindeer.to_vec());
+    Ok(Answer::from(furthest_distance))
 }
 
-lazy_static! {
-    static ref LINE_RE: Regex =
-        Regex::new(
-            r"^\w+ can fly (?P<fly_speed>\d+) km/s for (?P<fly_duration>\d+) seconds, but then must rest for (?P<rest_duration>\d+) seconds.$"
-        ).unwrap();
+pub fn part2(reindeer: &[Reindeer]) -> Result<Answer, Error> {
+    let winning_score = simulate_points_race(reindeer.to_vec());
+    Ok(Answer::from(winning_score))
 }
 
-fn parse_lines(lines: &Vec<String>) -> Vec<Reindeer> {
-    let mut reindeer = Vec::<Reindeer>::new();
-    for line in lines {
-        let caps = LINE_RE.captures(&line)
-            .expect(&format!("Input line did not match expected pattern. {}", line));
-        let fly_speed = caps.name("fly_speed").unwrap().as_str();
-        let fly_speed = fly_speed.parse::<u32>().expect("Invalid fly speed in line.");
-        let fly_duration = caps.name("fly_duration").unwrap().as_str();
-        let fly_duration = fly_duration.parse::<u32>().expect("Invalid fly duration in line.");
-        let rest_duration = caps.name("rest_duration").unwrap().as_str();
-        let rest_duration = rest_duration.parse::<u32>().expect("Invalid rest duration in line.");
-        let new_reindeer = Reindeer {
+fn parse_reindeer(line: &str) -> Result<Reindeer, Error> {
+    match extract_ints::<u32>(line)?[..] {
+        [fly_speed, fly_duration, rest_duration] => Ok(Reindeer {
             fly_speed,
             fly_duration,
             rest_duration,
@@ -42,10 +26,9 @@ fn parse_lines(lines: &Vec<String>) -> Vec<Reindeer> {
             flying: true,
             position_km: 0,
             points: 0
-        };
-        reindeer.push(new_reindeer);
+        }),
+        _ => Err(Error::parse("Expected a speed and two durations."))
     }
-    reindeer
 }
 
 fn simulate_sprint_race(reindeer: Vec<Reindeer>) -> u32 {
@@ -94,7 +77,8 @@ fn award_points(reindeer: &mut Vec<Reindeer>) {
     }
 }
 
-struct Reindeer {
+#[derive(Clone)]
+pub struct Reindeer {
     fly_speed: u32,
     fly_duration: u32,
     rest_duration: u32,
@@ -1,51 +1,32 @@
 use num;
-use regex::Regex;
-use crate::input_file::read_lines;
-use lazy_static::lazy_static;
+use crate::input_file::{extract_ints, parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let ingredients= parse_lines(&lines);
-    let best_score = best_cookie_score(&ingredients, None, None);
-    println!("{}", best_score);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Ingredient>, Error> {
+    parse_each_line(input, parse_ingredient)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let ingredients= parse_lines(&lines);
-    let best_score = best_cookie_score(&ingredients, None, Some(500));
-    println!("{}", best_score);
+pub fn part1(ingredients: &[Ingredient]) -> Result<Answer, Error> {
+    let best_score = best_cookie_score(ingredients, None, None);
+    Ok(Answer::from(best_score))
 }
 
-lazy_static! {
-    static ref LINE_RE: Regex =
-        Regex::new(
-            r"^\w+: capacity (?P<capacity>-?\d+), durability (?P<durability>-?\d+), flavor (?P<flavour>-?\d+), texture (?P<texture>-?\d+), calories (?P<calories>-?\d+)$"
-        ).unwrap();
+pub fn part2(ingredients: &[Ingredient]) -> Result<Answer, Error> {
+    let best_score = best_cookie_score(ingredients, None, Some(500));
+    Ok(Answer::from(best_score))
 }
 
-fn parse_lines(lines: &Vec<String>) -> Vec<Ingredient> {
-    let mut ingredients = Vec::<Ingredient>::new();
-    for line in lines {
-        let caps = LINE_RE.captures(&line)
-            .expect(&format!("Input line did not match expected pattern. {}", line));
-        let capacity = caps.name("capacity").unwrap().as_str();
-        let capacity = capacity.parse::<i32>().expect("Invalid capacity in line.");
-        let durability = caps.name("durability").unwrap().as_str();
-        let durability = durability.parse::<i32>().expect("Invalid durability in line.");
-        let flavour = caps.name("flavour").unwrap().as_str();
-        let flavour = flavour.parse::<i32>().expect("Invalid flavour in line.");
-        let texture = caps.name("texture").unwrap().as_str();
-        let texture = texture.parse::<i32>().expect("Invalid texture in line.");
-        let calories = caps.name("calories").unwrap().as_str();
-        let calories = calories.parse::<i32>().expect("Invalid calories in line.");
-        let ingredient = Ingredient {capacity, durability, flavour, texture, calories};
-        ingredients.push(ingredient);
+fn parse_ingredient(line: &str) -> Result<Ingredient, Error> {
+    match extract_ints::<i32>(line)?[..] {
+        [capacity, durability, flavour, texture, calories] => {
+            Ok(Ingredient {capacity, durability, flavour, texture, calories})
+        },
+        _ => Err(Error::parse("Expected five properties."))
     }
-    ingredients
 }
 
-struct Ingredient {
+pub struct Ingredient {
     capacity: i32,
     durability: i32,
     flavour: i32,
@@ -54,7 +35,7 @@ struct Ingredient {
 }
 
 fn best_cookie_score(
-    ingredients: &Vec<Ingredient>,
+    ingredients: &[Ingredient],
     amounts: Option<Vec<i32>>,
     total_calories: Option<i32>
 ) -> i32 {
@@ -95,7 +76,7 @@ fn best_cookie_score(
     best_score
 }
 
-fn cookie_score(ingredients: &Vec<Ingredient>, amounts: &Vec<i32>, total_calories: Option<i32>) -> i32 {
+fn cookie_score(ingredients: &[Ingredient], amounts: &Vec<i32>, total_calories: Option<i32>) -> i32 {
     let mut capacity_total = 0;
     let mut durability_total = 0;
     let mut flavour_total = 0;
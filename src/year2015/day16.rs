@@ -1,7 +1,10 @@
 use std::{collections::HashMap};
 
 use regex::Regex;
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::captures::{named, named_str, parse_captures};
+use crate::answer::Answer;
+use crate::error::Error;
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -19,23 +22,23 @@ lazy_static! {
     ];
 }
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let sues = parse_lines(&lines);
-    let sue_index = match_sue(sues, false);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<HashMap<String, u32>>, Error> {
+    parse_each_line(input, parse_sue)
+}
+
+pub fn part1(sues: &[HashMap<String, u32>]) -> Result<Answer, Error> {
+    let sue_index = match_sue(sues.to_vec(), false);
     match sue_index {
-        Some(i) => println!("{}", i),
-        None => panic!("No sue found that matches the target.")
+        Some(i) => Ok(Answer::from(i)),
+        None => Err(Error::solve("No sue found that matches the target."))
     }
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let sues = parse_lines(&lines);
-    let sue_index = match_sue(sues, true);
+pub fn part2(sues: &[HashMap<String, u32>]) -> Result<Answer, Error> {
+    let sue_index = match_sue(sues.to_vec(), true);
     match sue_index {
-        Some(i) => println!("{}", i),
-        None => panic!("No sue found that matches the target.")
+        Some(i) => Ok(Answer::from(i)),
+        None => Err(Error::solve("No sue found that matches the target."))
     }
 }
 
@@ -46,23 +49,16 @@ lazy_static! {
         ).unwrap();
 }
 
-fn parse_lines(lines: &Vec<String>) -> Vec<HashMap<String, u32>> {
-    let mut sues = Vec::<HashMap<String, u32>>::new();
-    for line in lines {
+fn parse_sue(line: &str) -> Result<HashMap<String, u32>, Error> {
+    parse_captures(&LINE_RE, line, |caps| {
         let mut properties = HashMap::<String, u32>::new();
-        let caps = LINE_RE.captures(&line)
-            .expect(&format!("Input line did not match expected pattern. {}", line));
         for i in 1..4 {
-            let regex_key_tag = format!("key{}", i);
-            let regex_val_tag = format!("val{}", i);
-            let key = caps.name(&regex_key_tag).unwrap().as_str();
-            let value = caps.name(&regex_val_tag).unwrap().as_str();
-            let value = value.parse::<u32>().expect("Invalid numeric value in line.");
+            let key = named_str(caps, &format!("key{}", i))?;
+            let value = named(caps, &format!("val{}", i))?;
             properties.insert(key.to_string(), value);
         }
-        sues.push(properties);
-    }
-    sues
+        Ok(properties)
+    })
 }
 
 fn match_sue(sues: Vec<HashMap<String, u32>>, inequalities: bool) -> Option<usize> {
@@ -1,37 +1,31 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let containers = parse_lines(&lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<i32>, Error> {
+    parse_lines(input)
+}
+
+pub fn part1(containers: &[i32]) -> Result<Answer, Error> {
     let containers_len = containers.len();
-    let combos = container_combos_for_amount(&containers, 150, containers_len);
-    println!("{}", combos);
+    let combos = container_combos_for_amount(containers, 150, containers_len);
+    Ok(Answer::from(combos))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let containers = parse_lines(&lines);
+pub fn part2(containers: &[i32]) -> Result<Answer, Error> {
     let containers_len = containers.len();
     let mut combos = 0;
     // Find the amount of combos for the smallest possible number of containers
     for limit in 1..containers_len {
-        combos = container_combos_for_amount(&containers, 150, limit);
+        combos = container_combos_for_amount(containers, 150, limit);
         if combos > 0 {
             break;
         }
     }
-    println!("{}", combos);
-}
-
-fn parse_lines(lines: &Vec<String>) -> Vec<i32> {
-    let mut containers = Vec::<i32>::new();
-    for line in lines {
-        containers.push(line.parse::<i32>().expect("Line does not parse as integer."))
-    }
-    containers
+    Ok(Answer::from(combos))
 }
 
-fn container_combos_for_amount(containers: &Vec<i32>, total: i32, container_limit: usize) -> i32 {
+fn container_combos_for_amount(containers: &[i32], total: i32, container_limit: usize) -> i32 {
     // Base case - total is negative, so we have overshot the target
     // or depth limit has been reached, so stop
     if total < 0 || container_limit == 0 {
@@ -1,39 +1,32 @@
 use crate::{
     data_structs::Grid,
-    input_file::read_lines
+    input_file::{read_lines, PuzzleInput}
 };
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let mut lights = parse_lines(&lines);
+pub fn parse(input: &PuzzleInput) -> Result<Grid<bool>, Error> {
+    Grid::from_lines(&read_lines(input)?, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(Error::parse("Invalid char in line, must be # or ."))
+    })
+}
+
+pub fn part1(lights: &Grid<bool>) -> Result<Answer, Error> {
+    let mut lights = lights.clone();
     for _ in 0..100 {
         lights = animate_lights(lights, false);
     }
-    println!("{}", get_total_lights_on(&lights));
+    Ok(Answer::from(get_total_lights_on(&lights)))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let mut lights = parse_lines(&lines);
+pub fn part2(lights: &Grid<bool>) -> Result<Answer, Error> {
+    let mut lights = lights.clone();
     for _ in 0..100 {
         lights = animate_lights(lights, true);
     }
-    println!("{}", get_total_lights_on(&lights));
-}
-
-fn parse_lines(lines: &Vec<String>) -> Grid<bool> {
-    let mut lights = Grid::<bool>::new(lines[0].len(), lines.len(), false);
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let light = match c {
-                '#' => true,
-                '.' => false,
-                 _ => panic!("Invalid char in line, must be # or .")
-            };
-            lights.set(x, y, light);
-        }
-    }
-    lights
+    Ok(Answer::from(get_total_lights_on(&lights)))
 }
 
 fn animate_lights(lights: Grid<bool>, corners_always_on: bool) -> Grid<bool> {
@@ -60,21 +53,7 @@ fn animate_lights(lights: Grid<bool>, corners_always_on: bool) -> Grid<bool> {
 }
 
 fn get_surrounding_lights_on(lights: &Grid<bool>, x: usize, y: usize) -> u32 {
-    let mut lights_on = 0;
-    for x1 in x.saturating_sub(1)..x+2 {
-        if x1 >= lights.xsize {
-            continue;
-        }
-        for y1 in y.saturating_sub(1)..y+2 {
-            if y1 >= lights.ysize {
-                continue;
-            }
-            if !(x == x1 && y == y1) && *lights.get(x1, y1) {
-                lights_on += 1;
-            }
-        }
-    }
-    lights_on
+    lights.neighbours8(x, y).filter(|(_, on)| **on).count() as u32
 }
 
 fn get_total_lights_on(lights: &Grid<bool>) -> u32 {
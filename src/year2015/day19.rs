@@ -3,51 +3,61 @@ use std::collections::{
     HashSet
 };
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each, parse_two_sections, PuzzleInput};
+use crate::answer::Answer;
+use crate::cancel;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (molecule, replacements, _) = parse_lines(&lines);
-    let count = count_distinct_replacements(&molecule, &replacements);
-    println!("{}", count);
+pub fn parse(input: &PuzzleInput) -> Result<Rules, Error> {
+    let sections = parse_two_sections(input, |lines| parse_each(lines, parse_replacement), parse_molecule)?;
+    Ok(collect_replacements(sections))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (molecule, _, reductions) = parse_lines(&lines);
-    let count = count_reductions_to_e(&molecule, &reductions);
-    println!("{}", count);
+pub fn part1(rules: &Rules) -> Result<Answer, Error> {
+    let count = count_distinct_replacements(&rules.molecule, &rules.replacements);
+    Ok(Answer::from(count))
 }
 
-fn parse_lines(lines: &Vec<String>) -> (String, HashMap<String, Vec<String>>, Vec<(String, String)>) {
+pub fn part2(rules: &Rules) -> Result<Answer, Error> {
+    let count = count_reductions_to_e(&rules.molecule, &rules.reductions)?;
+    Ok(Answer::from(count))
+}
+
+/// The medicine molecule, what each element can be replaced with,
+/// and each replacement the other way round, longest first.
+pub struct Rules {
+    molecule: String,
+    replacements: HashMap<String, Vec<String>>,
+    reductions: Vec<(String, String)>
+}
+
+fn parse_replacement(line: &str) -> Result<(String, String), Error> {
+    match line.split_once(" => ") {
+        Some((from, to)) if is_molecule(from) && is_molecule(to) => Ok((from.to_string(), to.to_string())),
+        _ => Err(Error::parse("Invalid syntax in replacement line."))
+    }
+}
+
+fn parse_molecule(lines: &[String]) -> Result<String, Error> {
+    match lines {
+        [molecule] if is_molecule(molecule) => Ok(molecule.to_string()),
+        _ => Err(Error::parse("Expected the molecule on a single line of letters."))
+    }
+}
+
+fn is_molecule(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn collect_replacements((pairs, molecule): (Vec<(String, String)>, String)) -> Rules {
     let mut replacements = HashMap::<String, Vec<String>>::new();
     let mut reductions = Vec::<(String, String)>::new();
-    let mut read_all_replacements = false;
-    let mut molecule: String = String::new();
-    for line in lines {
-        if line == "" {
-            read_all_replacements = true;
-            continue;
-        }
-        if read_all_replacements {
-            molecule = line.to_string();
-            continue;
-        }
-        let parts = line.split(" => ").collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            panic!("Invalid syntax in replacement line.");
-        }
-        reductions.push((parts[1].to_string(), parts[0].to_string()));
-        let entry = replacements.get_mut(parts[0]);
-        match entry {
-            None => {
-                replacements.insert(parts[0].to_string(), vec![parts[1].to_string()]);
-            },
-            Some(v) => v.push(parts[1].to_string()),
-        }
+    for (from, to) in pairs {
+        reductions.push((to.clone(), from.clone()));
+        replacements.entry(from).or_default().push(to);
     }
     reductions.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
-    (molecule, replacements, reductions)
+    Rules { molecule, replacements, reductions }
 }
 
 fn count_distinct_replacements(molecule: &str, replacements: &HashMap<String, Vec<String>>) -> usize {
@@ -70,8 +80,8 @@ fn count_distinct_replacements(molecule: &str, replacements: &HashMap<String, Ve
             None => {
                 // Didn't find a 1-character element
                 // Try looking up a 2-character element
-                let double_element = single_element.clone() + &molecule_chars[i+1].to_string();
-                match replacements.get(&double_element) {
+                let double_element = molecule.get(i..i+2).unwrap_or_default();
+                match replacements.get(double_element) {
                     Some(subs) => {
                         // Found it in the valid replacements - replace it and
                         // add it to the set.
@@ -92,17 +102,16 @@ fn count_distinct_replacements(molecule: &str, replacements: &HashMap<String, Ve
     resulting_molecules.len()
 }
 
-fn count_reductions_to_e(start_molecule: &str, reductions: &Vec<(String, String)>) -> usize {
+fn count_reductions_to_e(start_molecule: &str, reductions: &[(String, String)]) -> Result<usize, Error> {
     let mut molecule = start_molecule.to_string();
     let mut reduction_count = 0;
     while molecule != "e" {
-        for (pattern, result) in reductions {
-            if molecule.contains(pattern) {
-                molecule = molecule.replacen(pattern, result, 1);
-                reduction_count += 1;
-                break;
-            }
-        }
+        cancel::check()?;
+        let (pattern, result) = reductions.iter()
+            .find(|(pattern, _)| molecule.contains(pattern.as_str()))
+            .ok_or_else(|| Error::solve(format!("Can't reduce {} any further.", molecule)))?;
+        molecule = molecule.replacen(pattern.as_str(), result, 1);
+        reduction_count += 1;
     }
-    reduction_count
+    Ok(reduction_count)
 }
\ No newline at end of file
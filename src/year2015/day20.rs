@@ -1,33 +1,42 @@
+use std::convert::TryFrom;
 use std::collections::{HashMap, HashSet};
 use primes;
 
-use crate::input_file::read_all_to_string;
+use crate::input_file::{read_all_to_string, PuzzleInput};
+use crate::answer::Answer;
+use crate::cancel;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let target = read_all_to_string(input_file_path).parse::<u64>()
-        .expect("Input is not numeric.");
-    let house_num = first_to_target(target);
-    println!("{}", house_num);
+pub fn parse(input: &PuzzleInput) -> Result<u64, Error> {
+    let target = read_all_to_string(input)?.trim().parse::<u64>()?;
+    Ok(target)
 }
 
-pub fn part2(input_file_path: &str) {
-    let target = read_all_to_string(input_file_path).parse::<u32>()
-        .expect("Input is not numeric.");
-    let house = first_to_target_limited_presents(target);
-    println!("{}", house);
+pub fn part1(target: &u64) -> Result<Answer, Error> {
+    let house_num = first_to_target(*target)?;
+    Ok(Answer::from(house_num))
 }
 
-fn first_to_target(target: u64) -> u64 {
+pub fn part2(target: &u64) -> Result<Answer, Error> {
+    let target = u32::try_from(*target)
+        .map_err(|_| Error::solve("The target is too large."))?;
+    let house = first_to_target_limited_presents(target)
+        .ok_or_else(|| Error::solve(format!("No house below {} gets more than the target.", MAX_HOUSES)))?;
+    Ok(Answer::from(house))
+}
+
+fn first_to_target(target: u64) -> Result<u64, Error> {
     let mut factorizer = Factorizer::new();
     // Remove factor of 10
     for i in 2..u64::MAX {
+        cancel::check()?;
         let f = factorizer.get_factors(i).into_iter().collect::<HashSet<u64>>();
         let s = f.iter().sum::<u64>() * 10 + 10;
         if s >= target {
-            return i;
+            return Ok(i);
         }
     }
-    panic!("Didn't find an answer.");
+    Err(Error::solve("No house gets more than the target."))
 }
 
 struct Factorizer {
@@ -62,11 +71,10 @@ impl Factorizer {
 
 const MAX_HOUSES: usize = 10000000;
 
-fn first_to_target_limited_presents(target: u32) -> u32 {
+fn first_to_target_limited_presents(target: u32) -> Option<u32> {
     // need to use a vec so that it is heap allocated
     // stack is not large enough!
     let mut houses = vec![0; MAX_HOUSES];
-    let mut max_found = 0;
     for elf in 1..MAX_HOUSES+1 {
         for present in 1..50+1 {
             let address = elf * present;
@@ -78,12 +86,8 @@ fn first_to_target_limited_presents(target: u32) -> u32 {
     }
     for i in 0..MAX_HOUSES {
         if houses[i] > target {
-            return i as u32;
-        }
-        if houses[i] > max_found {
-            max_found = houses[i];
+            return Some(i as u32);
         }
     }
-    println!("{}", max_found);
-    panic!("No house found that exceeds target.");
+    None
 }
\ No newline at end of file
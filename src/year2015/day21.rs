@@ -1,12 +1,17 @@
 use std::cmp::max;
 
-use regex::Regex;
+use crate::input_file::{extract_ints, read_all_to_string, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-use crate::input_file::read_lines;
+pub fn parse(input: &PuzzleInput) -> Result<Fighter, Error> {
+    match extract_ints::<i32>(&read_all_to_string(input)?)?[..] {
+        [health, damage, armour] => Ok(Fighter { health, damage, armour }),
+        _ => Err(Error::parse("Expected hit points, damage and armor."))
+    }
+}
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let boss = parse_lines(lines);
+pub fn part1(boss: &Fighter) -> Result<Answer, Error> {
     let item_combos = get_item_combos();
     let mut lowest_cost_to_win: i32 = -1;
     for combo in item_combos {
@@ -22,14 +27,12 @@ pub fn part1(input_file_path: &str) {
         }
     }
     if lowest_cost_to_win == -1 {
-        panic!("Did not find a single combo of items that won.");
+        return Err(Error::solve("Did not find a single combo of items that won."));
     }
-    println!("{}", lowest_cost_to_win);
+    Ok(Answer::from(lowest_cost_to_win))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let boss = parse_lines(lines);
+pub fn part2(boss: &Fighter) -> Result<Answer, Error> {
     let item_combos = get_item_combos();
     let mut highest_cost_to_lose: i32 = -1;
     for combo in item_combos {
@@ -44,24 +47,9 @@ pub fn part2(input_file_path: &str) {
         }
     }
     if highest_cost_to_lose == -1 {
-        panic!("Did not find a single combo of items that lost.");
-    }
-    println!("{}", highest_cost_to_lose);
-}
-
-fn parse_lines(lines: Vec<String>) -> Fighter {
-    let number_regex = Regex::new(r"\d+").unwrap();
-    if lines.len() != 3 {
-        panic!("Wrong number of lines in input file.");
-    }
-    Fighter {
-        health: number_regex.find(&lines[0])
-            .unwrap().as_str().parse::<i32>().expect("Hit points not numeric"),
-        damage: number_regex.find(&lines[1])
-            .unwrap().as_str().parse::<i32>().expect("Damage not numeric"),
-        armour: number_regex.find(&lines[2])
-            .unwrap().as_str().parse::<i32>().expect("Armour not numeric"),
+        return Err(Error::solve("Did not find a single combo of items that lost."));
     }
+    Ok(Answer::from(highest_cost_to_lose))
 }
 
 fn get_item_combos() -> Vec<Item> {
@@ -126,7 +114,7 @@ fn get_rings_combos() -> Vec<Item> {
 }
 
 #[derive(Clone)]
-struct Fighter {
+pub struct Fighter {
     health: i32,
     damage: i32,
     armour: i32
@@ -1,122 +1,106 @@
 use std::{cmp::max, fmt::Display};
 
-use regex::Regex;
+use crate::input_file::{extract_ints, read_all_to_string, PuzzleInput};
+use crate::answer::Answer;
+use crate::search::{dijkstra, Path};
+use crate::error::Error;
 
-use crate::input_file::read_lines;
+const DEBUGGING: bool = false;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let boss = parse_lines(lines);
-    let player = Wizard { health: 50, mana: 500, armour: 0 };
-    let game = Game::new(player, boss, false);
-    let best_game = find_least_mana_win(&game).unwrap();
-    println!("Mana spent: {}", best_game.mana_spent);
-    println!("Spells cast:");
-    for spell in best_game.spells_cast {
-        println!(" -> {}", spell);
+pub fn parse(input: &PuzzleInput) -> Result<Boss, Error> {
+    match extract_ints::<i32>(&read_all_to_string(input)?)?[..] {
+        [health, damage] => Ok(Boss { health, damage }),
+        _ => Err(Error::parse("Expected the boss's hit points and damage."))
     }
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let boss = parse_lines(lines);
+pub fn part1(boss: &Boss) -> Result<Answer, Error> {
     let player = Wizard { health: 50, mana: 500, armour: 0 };
-    let game = Game::new(player, boss, true);
-    let best_game = find_least_mana_win(&game).unwrap();
-    println!("Mana spent: {}", best_game.mana_spent);
-    println!("Spells cast:");
-    for spell in best_game.spells_cast {
-        println!(" -> {}", spell);
+    let game = Game::new(player, boss.clone(), false);
+    let best_path = find_least_mana_win(&game)?
+        .ok_or_else(|| Error::solve("The wizard can't win."))?;
+    if DEBUGGING {
+        print_spells_cast(&best_path);
     }
+    Ok(Answer::from(best_path.cost))
 }
 
-fn parse_lines(lines: Vec<String>) -> Boss {
-    let number_regex = Regex::new(r"\d+").unwrap();
-    if lines.len() != 2 {
-        panic!("Wrong number of lines in input file.");
-    }
-    Boss {
-        health: number_regex.find(&lines[0])
-            .unwrap().as_str().parse::<i32>().expect("Hit points not numeric"),
-        damage: number_regex.find(&lines[1])
-            .unwrap().as_str().parse::<i32>().expect("Damage not numeric"),
+pub fn part2(boss: &Boss) -> Result<Answer, Error> {
+    let player = Wizard { health: 50, mana: 500, armour: 0 };
+    let game = Game::new(player, boss.clone(), true);
+    let best_path = find_least_mana_win(&game)?
+        .ok_or_else(|| Error::solve("The wizard can't win."))?;
+    if DEBUGGING {
+        print_spells_cast(&best_path);
     }
+    Ok(Answer::from(best_path.cost))
 }
 
-fn find_least_mana_win(game: &Game) -> Option<Game> {
-    let mut best_game: Option<Game> = None;
+/// Search the games that can follow on from this one, cheapest
+/// first, for the one the wizard wins spending the least mana.
+fn find_least_mana_win(game: &Game) -> Result<Option<Path<Game, i32>>, Error> {
+    dijkstra(game.clone(), next_games, |game| game.boss.health <= 0)
+}
+
+/// Every game that can follow on from a round of this one
+/// without the boss winning, and the mana spent in the round.
+fn next_games(game: &Game) -> Vec<(Game, i32)> {
     // Iterate through each available spell to cast and try casting it.
-    for spell in game.possible_spells() {
-        let mut game_next = game.clone();
-        game_next.spells_cast.push(spell);
-        // Take the player's turn
-        let end_state = game_next.player_turn(spell);
-        // Check if the game is over after the player's turn
-        match end_state {
-            GameEndState::Ongoing => (),
-            GameEndState::BossWon => continue,
-            GameEndState::WizardWon => {
-                best_game = compare_games(best_game, Some(game_next.clone()));
-                continue;
-            }
-        }
-        // Take the boss's turn
-        let end_state = game_next.boss_turn();
-        // Check if the game is over
-        match end_state {
-            GameEndState::Ongoing => (),
-            GameEndState::BossWon => continue,
-            GameEndState::WizardWon => {
-                best_game = compare_games(best_game, Some(game_next.clone()));
-                continue;
-            }
-        }
-        // If the battle is still going, recurse.
-        let best_game_next = find_least_mana_win(&game_next);
-        // Update the best game if one was found that uses less mana
-        best_game = compare_games(best_game, best_game_next);
+    game.possible_spells()
+        .into_iter()
+        .filter_map(|spell| play_round(game, spell).map(|game_next| (game_next, spell.cost())))
+        .collect()
+}
+
+/// The game after a round where the wizard casts the
+/// spell, or None if the boss wins the round.
+fn play_round(game: &Game, spell: Spell) -> Option<Game> {
+    let mut game_next = game.clone();
+    // Take the player's turn, then the boss's if the game isn't over
+    let mut end_state = game_next.player_turn(spell);
+    if end_state == GameEndState::Ongoing {
+        end_state = game_next.boss_turn();
+    }
+    if end_state == GameEndState::BossWon {
+        None
+    } else {
+        Some(game_next)
     }
-    best_game
 }
 
-/// Get the game from the two that uses less mana
-fn compare_games(game1: Option<Game>, game2: Option<Game>) -> Option<Game> {
-    match (game1, game2) {
-        (None, None) => None,
-        (Some(g1), None) => Some(g1),
-        (None, Some(g2)) => Some(g2),
-        (Some(g1), Some(g2)) => {
-            if g2.mana_spent < g1.mana_spent {
-                Some(g2)
-            } else {
-                Some(g1)
-            }
-        }
+/// Print the spells cast along the path, working out each one
+/// from the games before and after the round it was cast in.
+fn print_spells_cast(path: &Path<Game, i32>) {
+    println!("Spells cast:");
+    for games in path.nodes.windows(2) {
+        let spell = games[0].possible_spells()
+            .into_iter()
+            .find(|spell| play_round(&games[0], *spell).as_ref() == Some(&games[1]))
+            .expect("Each game on the path follows a round of the last.");
+        println!(" -> {}", spell);
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Wizard {
     health: i32,
     mana: i32,
     armour: i32
 }
 
-#[derive(Clone)]
-struct Boss {
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Boss {
     health: i32,
     damage: i32
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Game {
     player: Wizard,
     boss: Boss,
     shield_effect_remaining: i32,
     poison_effect_remaining: i32,
     recharge_effect_remaining: i32,
-    mana_spent: i32,
-    spells_cast: Vec<Spell>,
     hard_mode: bool
 }
 
@@ -128,8 +112,6 @@ impl Game {
             shield_effect_remaining: 0,
             poison_effect_remaining: 0,
             recharge_effect_remaining: 0,
-            mana_spent: 0,
-            spells_cast: vec![],
             hard_mode
         }
     }
@@ -147,7 +129,6 @@ impl Game {
             return GameEndState::WizardWon;
         }
         self.player.mana -= spell_to_cast.cost();
-        self.mana_spent += spell_to_cast.cost();
         match spell_to_cast {
             Spell::MagicMissile => self.cast_magic_missile(),
             Spell::Drain        => self.cast_drain(),
@@ -255,7 +236,7 @@ impl Game {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum Spell {
     MagicMissile,
     Drain,
@@ -289,6 +270,7 @@ impl Display for Spell {
     }
 }
 
+#[derive(PartialEq)]
 enum GameEndState {
     Ongoing,
     WizardWon,
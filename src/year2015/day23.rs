@@ -1,29 +1,31 @@
 use std::collections::HashMap;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, parse_value, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instructions = parse_lines(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Instruction>, Error> {
+    parse_each_line(input, parse_instruction)
+}
+
+pub fn part1(instructions: &[Instruction]) -> Result<Answer, Error> {
     let mut registers = HashMap::<String, u32>::new();
     registers.insert("a".to_string(), 0);
     registers.insert("b".to_string(), 0);
     let output = run_program(instructions, &registers);
-    println!("{}", output["b"]);
+    Ok(Answer::from(output["b"]))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instructions = parse_lines(lines);
+pub fn part2(instructions: &[Instruction]) -> Result<Answer, Error> {
     let mut registers = HashMap::<String, u32>::new();
     registers.insert("a".to_string(), 1);
     registers.insert("b".to_string(), 0);
     let output = run_program(instructions, &registers);
-    println!("{}", output["b"]);
+    Ok(Answer::from(output["b"]))
 }
 
 fn run_program(
-    instructions: Vec<Instruction>,
+    instructions: &[Instruction],
     init_registers: &HashMap<String, u32>
 ) -> HashMap<String, u32> {
     let mut program_counter = 0isize;
@@ -90,7 +92,7 @@ fn run_program(
     registers
 }
 
-enum Instruction {
+pub enum Instruction {
     Half(String),
     Triple(String),
     Increment(String),
@@ -99,66 +101,66 @@ enum Instruction {
     JumpIfOne(String, isize)
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<Instruction> {
-    let mut instructions = Vec::<Instruction>::with_capacity(lines.len());
-    for line in lines {
-        let line = line.replace(",", "");
-        let tokens = line.split(' ').collect::<Vec<&str>>();
-        if tokens.is_empty() {
-            panic!("No instruction on line.");
-        }
-        let instruction = match tokens[0] {
-            "hlf" => parse_hlf(&tokens[1..]),
-            "tpl" => parse_tpl(&tokens[1..]),
-            "inc" => parse_inc(&tokens[1..]),
-            "jmp" => parse_jmp(&tokens[1..]),
-            "jie" => parse_jie(&tokens[1..]),
-            "jio" => parse_jio(&tokens[1..]),
-            _ => panic!("Unknown instruction in list.")
-        };
-        instructions.push(instruction);
+fn parse_instruction(line: &str) -> Result<Instruction, Error> {
+    let line = line.replace(",", "");
+    let tokens = line.split(' ').collect::<Vec<&str>>();
+    match tokens[0] {
+        "hlf" => parse_hlf(&tokens[1..]),
+        "tpl" => parse_tpl(&tokens[1..]),
+        "inc" => parse_inc(&tokens[1..]),
+        "jmp" => parse_jmp(&tokens[1..]),
+        "jie" => parse_jie(&tokens[1..]),
+        "jio" => parse_jio(&tokens[1..]),
+        _ => Err(Error::parse("Unknown instruction in list."))
     }
-    instructions
 }
 
-fn parse_hlf(args: &[&str]) -> Instruction {
-    if args.len() != 1 {
-        panic!("Wrong number of args to 'hlf'");
+fn parse_hlf(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [r] => Ok(Instruction::Half(parse_register(r)?)),
+        _ => Err(Error::parse("Wrong number of args to 'hlf'"))
     }
-    Instruction::Half(args[0].to_string())
 }
 
-fn parse_tpl(args: &[&str]) -> Instruction {
-    if args.len() != 1 {
-        panic!("Wrong number of args to 'tpl'");
+fn parse_tpl(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [r] => Ok(Instruction::Triple(parse_register(r)?)),
+        _ => Err(Error::parse("Wrong number of args to 'tpl'"))
     }
-    Instruction::Triple(args[0].to_string())
 }
 
-fn parse_inc(args: &[&str]) -> Instruction {
-    if args.len() != 1 {
-        panic!("Wrong number of args to 'inc'");
+fn parse_inc(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [r] => Ok(Instruction::Increment(parse_register(r)?)),
+        _ => Err(Error::parse("Wrong number of args to 'inc'"))
     }
-    Instruction::Increment(args[0].to_string())
 }
 
-fn parse_jmp(args: &[&str]) -> Instruction {
-    if args.len() != 1 {
-        panic!("Wrong number of args to 'jmp'");
+fn parse_jmp(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [offset] => Ok(Instruction::Jump(parse_value(offset)?)),
+        _ => Err(Error::parse("Wrong number of args to 'jmp'"))
     }
-    Instruction::Jump(args[0].to_string().parse::<isize>().unwrap())
 }
 
-fn parse_jie(args: &[&str]) -> Instruction {
-    if args.len() != 2 {
-        panic!("Wrong number of args to 'jie'");
+fn parse_jie(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [r, offset] => Ok(Instruction::JumpIfEven(parse_register(r)?, parse_value(offset)?)),
+        _ => Err(Error::parse("Wrong number of args to 'jie'"))
     }
-    Instruction::JumpIfEven(args[0].to_string(), args[1].to_string().parse::<isize>().unwrap())
 }
 
-fn parse_jio(args: &[&str]) -> Instruction {
-    if args.len() != 2 {
-        panic!("Wrong number of args to 'jio'");
+fn parse_jio(args: &[&str]) -> Result<Instruction, Error> {
+    match args {
+        [r, offset] => Ok(Instruction::JumpIfOne(parse_register(r)?, parse_value(offset)?)),
+        _ => Err(Error::parse("Wrong number of args to 'jio'"))
     }
-    Instruction::JumpIfOne(args[0].to_string(), args[1].to_string().parse::<isize>().unwrap())
-}
\ No newline at end of file
+}
+
+/// The computer only has registers a and b.
+fn parse_register(arg: &str) -> Result<String, Error> {
+    match arg {
+        "a" | "b" => Ok(arg.to_string()),
+        _ => Err(Error::parse(format!("Register {} does not exist.", arg)))
+    }
+}
@@ -1,45 +1,40 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
 use itertools::Itertools;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let presents = parse_lines(lines);
-    let qe = find_best_passenger_qe(presents, 3);
-    println!("{}", qe);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<u64>, Error> {
+    parse_lines(input)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let presents = parse_lines(lines);
-    let qe = find_best_passenger_qe(presents, 4);
-    println!("{}", qe);
+pub fn part1(presents: &[u64]) -> Result<Answer, Error> {
+    let qe = find_best_passenger_qe(presents.to_vec(), 3)?;
+    Ok(Answer::from(qe))
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<u64> {
-    let mut presents = Vec::<u64>::with_capacity(lines.len());
-    for line in lines {
-        presents.push(line.parse::<u64>().expect("Line did not parse as integer."))
-    }
-    presents
+pub fn part2(presents: &[u64]) -> Result<Answer, Error> {
+    let qe = find_best_passenger_qe(presents.to_vec(), 4)?;
+    Ok(Answer::from(qe))
 }
 
-fn find_best_passenger_qe(presents: Vec<u64>, compartments: usize) -> u64 {
-    let target_weight = target_weight(&presents, compartments);
+fn find_best_passenger_qe(presents: Vec<u64>, compartments: usize) -> Result<u64, Error> {
+    let target_weight = target_weight(&presents, compartments)?;
     let passenger_combos = find_smallest_combos(presents, target_weight);
-    best_quantum_entanglement(passenger_combos)  
+    best_quantum_entanglement(passenger_combos)
+        .ok_or_else(|| Error::solve(format!("No few enough presents weigh {} between them.", target_weight)))
 }
 
-fn target_weight(presents: &Vec<u64>, compartments: usize) -> u64 {
+fn target_weight(presents: &Vec<u64>, compartments: usize) -> Result<u64, Error> {
     // add up all numbers and divide by 3 to get amount each section has to weigh
     let mut sum = 0;
     for p in presents {
         sum += p
     }
     if sum % compartments as u64 != 0 {
-        panic!("Impossible to balance.")
+        return Err(Error::solve("Impossible to balance."));
     }
-    sum / compartments as u64
+    Ok(sum / compartments as u64)
 }
 
 fn find_smallest_combos(presents: Vec<u64>, target_weight: u64) -> Vec<Vec<u64>> {
@@ -58,14 +53,14 @@ fn find_smallest_combos(presents: Vec<u64>, target_weight: u64) -> Vec<Vec<u64>>
     best_combos
 }
 
-fn best_quantum_entanglement(combos: Vec<Vec<u64>>) -> u64 {
+fn best_quantum_entanglement(combos: Vec<Vec<u64>>) -> Option<u64> {
     // find combo with the best quantum entanglement
-    let mut best_qe: u64 = combos[0].iter().product();
+    let mut best_qe: u64 = combos.first()?.iter().product();
     for combo in combos {
         let qe: u64 = combo.iter().product();
         if qe < best_qe {
             best_qe = qe;
         }
     }
-    best_qe
+    Some(best_qe)
 }
\ No newline at end of file
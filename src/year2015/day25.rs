@@ -1,33 +1,31 @@
-use crate::{data_structs::Coord, input_file::read_all_to_string};
+use crate::{data_structs::Coord, input_file::{extract_ints, read_all_to_string, PuzzleInput}};
+use crate::answer::Answer;
+use crate::cancel;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let pos = parse_input(input);
-    let code = find_code(&pos);
-    println!("{}", code);
+pub fn parse(input: &PuzzleInput) -> Result<Coord, Error> {
+    match extract_ints::<usize>(&read_all_to_string(input)?)?[..] {
+        [row, column] if row > 0 && column > 0 => Ok(Coord { x: column, y: row }),
+        _ => Err(Error::parse("Expected the row and column of the code, counting from 1."))
+    }
 }
 
-fn parse_input(input: String) -> Coord {
-    let parts = input.split_whitespace().collect::<Vec<&str>>();
-    let mut x = parts[17].chars();
-    x.next_back();
-    let x = x.as_str().parse().expect("Could not parse input row.");
-    let mut y = parts[15].chars();
-    y.next_back();
-    let y = y.as_str().parse().expect("Could not parse input col.");
-    Coord { x, y }
+pub fn part1(pos: &Coord) -> Result<Answer, Error> {
+    let code = find_code(pos)?;
+    Ok(Answer::from(code))
 }
 
-fn find_code(pos: &Coord) -> u64 {
+fn find_code(pos: &Coord) -> Result<u64, Error> {
     let mut x = 0;
     let mut y = 1;
     let mut start_y = 1;
     let mut code = 20151125;
     loop {
+        cancel::check()?;
         while y >= 0 {
             code = (code * 252533) % 33554393;
             if pos.x == (x + 1) as usize && pos.y == (y + 1) as usize {
-                return code;
+                return Ok(code);
             }
             x += 1;
             y -= 1;
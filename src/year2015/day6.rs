@@ -1,54 +1,60 @@
 use regex::Regex;
 use lazy_static::lazy_static;
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::captures::{group, group_str, parse_captures};
 use crate::data_structs::{Coord, Grid};
+use crate::answer::Answer;
+use crate::error::Error;
 
 /// Represents a single command for switching on/off
 /// lights in the light matrix.
-enum Command {
+pub enum Command {
     Toggle(Coord, Coord),
     TurnOn(Coord, Coord),
     TurnOff(Coord, Coord)
 }
 
+/// Parse each line of the input file into a Command.
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Command>, Error> {
+    parse_each_line(input, parse_instruction)
+}
+
 /// Set a matrix of lights on or off depending on
-/// instructions in the input file, and then calculate
+/// the instructions, and then calculate
 /// how many lights are lit.
-pub fn part1(input_file_path: &str) {
-    let instructions = read_lines(input_file_path);
+pub fn part1(instructions: &[Command]) -> Result<Answer, Error> {
     let mut lights: Grid<bool> =
         Grid::new(1000, 1000, false);
     // Execute the instructions in the file.
     for instruction in instructions {
-        match parse_instruction(&instruction) {
-            Command::Toggle(from, to) => toggle_lights(&mut lights, from, to),
-            Command::TurnOn(from, to) => turn_on_lights(&mut lights, from, to),
-            Command::TurnOff(from, to) => turn_off_lights(&mut lights, from, to),
+        match instruction {
+            Command::Toggle(from, to) => toggle_lights(&mut lights, from.clone(), to.clone()),
+            Command::TurnOn(from, to) => turn_on_lights(&mut lights, from.clone(), to.clone()),
+            Command::TurnOff(from, to) => turn_off_lights(&mut lights, from.clone(), to.clone()),
         }
     }
     // Count the lights switched on.
     let on_count = count_lights_on(&lights);
-    println!("{}", on_count);
+    Ok(Answer::from(on_count))
 }
 
 /// Set the brightness of matrix of lights depending on
-/// instructions in the input file, and then calculate
+/// the instructions, and then calculate
 /// total brightness of all lights.
-pub fn part2(input_file_path: &str) {
-    let instructions = read_lines(input_file_path);
+pub fn part2(instructions: &[Command]) -> Result<Answer, Error> {
     let mut lights: Grid<u32> =
         Grid::new(1000, 1000, 0);
     // Execute the instructions in the file.
     for instruction in instructions {
-        match parse_instruction(&instruction) {
-            Command::Toggle(from, to) => brighten_lights(&mut lights, from, to, 2),
-            Command::TurnOn(from, to) => brighten_lights(&mut lights, from, to, 1),
-            Command::TurnOff(from, to) => dim_lights(&mut lights, from, to, 1),
+        match instruction {
+            Command::Toggle(from, to) => brighten_lights(&mut lights, from.clone(), to.clone(), 2),
+            Command::TurnOn(from, to) => brighten_lights(&mut lights, from.clone(), to.clone(), 1),
+            Command::TurnOff(from, to) => dim_lights(&mut lights, from.clone(), to.clone(), 1),
         }
     }
     // Count the lights switched on.
     let total_brightness = sum_total_brightness(&lights);
-    println!("{}", total_brightness);
+    Ok(Answer::from(total_brightness))
 }
 
 // Set up regex as static so it's not recompiled every
@@ -60,52 +66,20 @@ lazy_static! {
 
 /// Parse a single instruction from the input file into
 /// a Command object.
-fn parse_instruction(line: &str) -> Command {
-    match INSTR_RE.captures(&line) {
-        None => panic!("Line in input file is not of expected format."),
-        Some(caps) => {
-            let command_str = caps.get(1).expect("No command found in line.").as_str();
-            // Convert captures to the correct format
-            let x1 = caps.get(2)
-                .expect("No x1 found in line.")
-                .as_str()
-                .parse::<usize>()
-                .expect("x1 was not a positive integer in line.");
-            let y1 = caps.get(3)
-                .expect("No y1 found in line.")
-                .as_str()
-                .parse::<usize>()
-                .expect("y1 was not a positive integer in line.");
-            let x2 = caps.get(4)
-                .expect("No x2 found in line.")
-                .as_str()
-                .parse::<usize>()
-                .expect("x2 was not a positive integer in line.");
-            let y2 = caps.get(5)
-                .expect("No y2 found in line.")
-                .as_str()
-                .parse::<usize>()
-                .expect("y2 was not a positive integer in line.");
-            return match command_str {
-                "toggle" =>
-                    Command::Toggle(
-                        Coord {x: x1, y: y1},
-                        Coord {x: x2, y: y2}
-                    ),
-                "turn on" =>
-                    Command::TurnOn(
-                        Coord {x: x1, y: y1},
-                        Coord {x: x2, y: y2}
-                    ),
-                "turn off" =>
-                    Command::TurnOff(
-                        Coord {x: x1, y: y1},
-                        Coord {x: x2, y: y2}
-                    ),
-                _ => panic!("Invalid command found in instruction.")
-            }
+fn parse_instruction(line: &str) -> Result<Command, Error> {
+    parse_captures(&INSTR_RE, line, |caps| {
+        // Convert captures to the correct format
+        let from = Coord {x: group(caps, 2)?, y: group(caps, 3)?};
+        let to = Coord {x: group(caps, 4)?, y: group(caps, 5)?};
+        if to.x >= 1000 || to.y >= 1000 || from.x > to.x || from.y > to.y {
+            return Err(Error::parse("The lights must be a range within 1000x1000."));
         }
-    }
+        match group_str(caps, 1)? {
+            "toggle" => Ok(Command::Toggle(from, to)),
+            "turn on" => Ok(Command::TurnOn(from, to)),
+            _ => Ok(Command::TurnOff(from, to))
+        }
+    })
 }
 
 /// Given a range of coordinates into the light matrix,
@@ -161,20 +135,10 @@ fn dim_lights(grid: &mut Grid<u32>, start: Coord, end: Coord, amount: u32) {
 
 /// Count how many lights are on in the given light matrix.
 fn count_lights_on(grid: &Grid<bool>) -> u32 {
-    let mut count: u32 = 0;
-    for light in grid {
-        if *light {
-            count += 1;
-        }
-    }
-    return count;
+    grid.into_iter().filter(|light| **light).count() as u32
 }
 
 /// Calculate the total brightness of all the lights in the matrix.
 fn sum_total_brightness(grid: &Grid<u32>) -> u32 {
-    let mut total: u32 = 0;
-    for light in grid {
-        total += *light;
-    }
-    return total;
+    grid.into_iter().sum()
 }
\ No newline at end of file
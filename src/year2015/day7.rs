@@ -1,31 +1,40 @@
 use regex::Regex;
 use lazy_static::lazy_static;
-use std::collections::HashMap;
-use crate::input_file::read_lines;
+use std::collections::{HashMap, HashSet};
+use crate::input_file::{parse_each, read_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let conn_strs = read_lines(input_file_path);
-    let mut conns = Vec::with_capacity(conn_strs.len());
-    for conn_str in conn_strs {
-        conns.push(parse_connection(conn_str));
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Connection>, Error> {
+    let lines = read_lines(input)?;
+    let conns = parse_each(&lines, parse_connection)?;
+    // Every wire read from must be driven by some connection.
+    let driven: HashSet<&str> = conns.iter().map(|conn| conn.target.as_str()).collect();
+    for (i, conn) in conns.iter().enumerate() {
+        if let Some(wire) = conn.source.references().find(|wire| !driven.contains(wire)) {
+            return Err(Error::parse(format!("Wire {} is used but never gets a signal.", wire)).at_line(i + 1, &lines[i]));
+        }
     }
-    let resolved_signals = resolve_connections(&conns, None);
-    println!("{}", resolved_signals["a"]);
+    Ok(conns)
 }
 
-pub fn part2(input_file_path: &str) {
-    let conn_strs = read_lines(input_file_path);
-    let mut conns = Vec::with_capacity(conn_strs.len());
-    for conn_str in conn_strs {
-        conns.push(parse_connection(conn_str));
-    }
-    let resolved_signals = resolve_connections(&conns, None);
-    let a = resolved_signals["a"];
-    let reresolved_signals = resolve_connections(&conns, Some(a));
-    println!("{}", reresolved_signals["a"]);
+pub fn part1(conns: &[Connection]) -> Result<Answer, Error> {
+    let resolved_signals = resolve_connections(conns, None);
+    Ok(Answer::from(signal_on_a(&resolved_signals)?))
 }
 
-fn resolve_connections(conns: &Vec<Connection>, b_override: Option<u16>) -> HashMap<String, u16> {
+pub fn part2(conns: &[Connection]) -> Result<Answer, Error> {
+    let resolved_signals = resolve_connections(conns, None);
+    let a = signal_on_a(&resolved_signals)?;
+    let reresolved_signals = resolve_connections(conns, Some(a));
+    Ok(Answer::from(signal_on_a(&reresolved_signals)?))
+}
+
+fn signal_on_a(resolved_signals: &HashMap<String, u16>) -> Result<u16, Error> {
+    resolved_signals.get("a").copied().ok_or_else(|| Error::solve("Wire a never gets a signal."))
+}
+
+fn resolve_connections(conns: &[Connection], b_override: Option<u16>) -> HashMap<String, u16> {
     let mut resolved_signals = HashMap::new();
     match b_override {
         Some(val) => {
@@ -92,7 +101,7 @@ fn resolve_connections(conns: &Vec<Connection>, b_override: Option<u16>) -> Hash
                     match (result1, result2) {
                         (Some(v1), Some(v2)) => {
                             if !resolved_signals.contains_key(&conn.target) {
-                                resolved_signals.insert(conn.target.clone(), v1 << v2);
+                                resolved_signals.insert(conn.target.clone(), v1.checked_shl(v2.into()).unwrap_or(0));
                                 modified_this_pass = true;
                             }
                         },
@@ -105,7 +114,7 @@ fn resolve_connections(conns: &Vec<Connection>, b_override: Option<u16>) -> Hash
                     match (result1, result2) {
                         (Some(v1), Some(v2)) => {
                             if !resolved_signals.contains_key(&conn.target) {
-                                resolved_signals.insert(conn.target.clone(), v1 >> v2);
+                                resolved_signals.insert(conn.target.clone(), v1.checked_shr(v2.into()).unwrap_or(0));
                                 modified_this_pass = true;
                             }
                         },
@@ -140,12 +149,29 @@ enum LogicExpression {
     RShift(BaseExpression, BaseExpression)
 }
 
+impl LogicExpression {
+    /// The wires the expression reads from.
+    fn references(&self) -> impl Iterator<Item = &str> {
+        let (e1, e2) = match self {
+            LogicExpression::Base(e) | LogicExpression::NotGate(e) => (e, None),
+            LogicExpression::AndGate(e1, e2)
+            | LogicExpression::OrGate(e1, e2)
+            | LogicExpression::LShift(e1, e2)
+            | LogicExpression::RShift(e1, e2) => (e1, Some(e2))
+        };
+        std::iter::once(e1).chain(e2).filter_map(|e| match e {
+            BaseExpression::Reference(r) => Some(r.as_str()),
+            BaseExpression::Constant(_) => None
+        })
+    }
+}
+
 enum BaseExpression {
     Constant(u16),
     Reference(String),
 }
 
-struct Connection {
+pub struct Connection {
     source: LogicExpression,
     target: String,
 }
@@ -157,9 +183,9 @@ lazy_static! {
         ).unwrap();
 }
 
-fn parse_connection(connection_str: String) -> Connection {
-    let caps = CONNECTION_RE.captures(&connection_str)
-        .expect(&format!("Input line did not match expected pattern. {}", connection_str));
+fn parse_connection(connection_str: &str) -> Result<Connection, Error> {
+    let caps = CONNECTION_RE.captures(connection_str)
+        .ok_or_else(|| Error::parse("Input line did not match expected pattern."))?;
     let not;
     // Check if NOT included
     match caps.name("not") {
@@ -167,13 +193,13 @@ fn parse_connection(connection_str: String) -> Connection {
         _ => not = false
     }
     // Parse first constant/reference term
-    let lexpr = parse_base_expression(caps.name("lconst"), caps.name("lref"));
+    let lexpr = parse_base_expression(caps.name("lconst"), caps.name("lref"))?;
     // Check for a binary operation
     let binop = match caps.name("binop") {
         Some(m) => {
             // Don't allow NOT in combination with a binary operator.
             if not {
-                panic!("NOT not supported for complex expressions.");
+                return Err(Error::parse("NOT not supported for complex expressions."));
             }
             Some(m.as_str())
         },
@@ -184,13 +210,13 @@ fn parse_connection(connection_str: String) -> Connection {
             // If there is a binary op, parse the second constant/reference term
             // and build the appropriate binary logic expression from the first and
             // second terms.
-            let rexpr = parse_base_expression(caps.name("rconst"), caps.name("rref"));
+            let rexpr = parse_base_expression(caps.name("rconst"), caps.name("rref"))?;
             match op {
                 "AND" => LogicExpression::AndGate(lexpr, rexpr),
                 "OR" => LogicExpression::OrGate(lexpr, rexpr),
                 "LSHIFT" => LogicExpression::LShift(lexpr, rexpr),
                 "RSHIFT" => LogicExpression::RShift(lexpr, rexpr),
-                _ => panic!("Unsupported binary operation in line.")
+                _ => return Err(Error::parse(format!("Unsupported binary operation {} in line.", op)))
             }
         },
         None => {
@@ -205,27 +231,31 @@ fn parse_connection(connection_str: String) -> Connection {
         }
     };
     // The target of the connection is a basic reference.
-    let target = caps.name("dest").expect("No destination found in line.").as_str().to_owned();
+    let target = caps.name("dest")
+        .ok_or_else(|| Error::parse("No destination found in line."))?
+        .as_str()
+        .to_owned();
     // Build the connection from the parsed components.
-    Connection {source, target}
+    Ok(Connection {source, target})
 }
 
 fn parse_base_expression(
     const_match: Option<regex::Match>,
     ref_match: Option<regex::Match>
-) -> BaseExpression {
+) -> Result<BaseExpression, Error> {
     match const_match {
         Some(m) => {
-            let value = m.as_str().parse::<u16>().expect("Constant is not 16 bit unsigned integer.");
-            BaseExpression::Constant(value)
+            let value = m.as_str().parse::<u16>()
+                .map_err(|_| Error::parse(format!("Constant {} is not a 16 bit unsigned integer.", m.as_str())))?;
+            Ok(BaseExpression::Constant(value))
         },
         None => {
             match ref_match {
                 Some(m) => {
                     let reference = m.as_str().to_owned();
-                    BaseExpression::Reference(reference)
+                    Ok(BaseExpression::Reference(reference))
                 },
-                None => panic!("Unhandled expression type.")
+                None => Err(Error::parse("Unhandled expression type."))
             }
         }
     }
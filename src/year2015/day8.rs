@@ -1,21 +1,50 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let strings = read_lines(input_file_path);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    parse_each_line(input, parse_string_literal)
+}
+
+pub fn part1(strings: &[String]) -> Result<Answer, Error> {
     let mut total = 0;
     for s in strings {
         total += s.len() - in_memory_len(&s);
     }
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-pub fn part2(input_file_path: &str) {
-    let strings = read_lines(input_file_path);
+pub fn part2(strings: &[String]) -> Result<Answer, Error> {
     let mut total = 0;
     for s in strings {
         total += encoded_len(&s) - s.len();
     }
-    println!("{}", total);
+    Ok(Answer::from(total))
+}
+
+/// Check the line is a string literal in double quotes, with
+/// only \\, \" and \x followed by two hex digits as escapes.
+fn parse_string_literal(line: &str) -> Result<String, Error> {
+    if line.len() < 2 || !line.starts_with('"') || !line.ends_with('"') {
+        return Err(Error::parse("Expected a string in double quotes."));
+    }
+    let mut chars = line[1..line.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        let valid = match c {
+            '\\' => match chars.next() {
+                Some('\\') | Some('"') => true,
+                Some('x') => chars.next().is_some_and(|c| c.is_ascii_hexdigit()) &&
+                    chars.next().is_some_and(|c| c.is_ascii_hexdigit()),
+                _ => false
+            },
+            '"' => return Err(Error::parse("Unescaped \" inside the string.")),
+            _ => true
+        };
+        if !valid {
+            return Err(Error::parse("Unsupported escape sequence."));
+        }
+    }
+    Ok(line.to_string())
 }
 
 fn in_memory_len(sequence: &str) -> usize {
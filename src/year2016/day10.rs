@@ -1,22 +1,32 @@
-use crate::input_file::read_lines;
+use crate::input_file::{read_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let mut system = balance_bots::System::new_from_rules(&lines);
-    println!("{}", system.run(17, 61));
+pub fn parse(input: &PuzzleInput) -> Result<balance_bots::System, Error> {
+    let lines = read_lines(input)?;
+    balance_bots::System::new_from_rules(&lines)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let mut system = balance_bots::System::new_from_rules(&lines);
-    system.run(-1, -1);
-    println!("{}", system.get_output(0) * system.get_output(1) * system.get_output(2))
+pub fn part1(system: &balance_bots::System) -> Result<Answer, Error> {
+    let mut system = system.clone();
+    let bot = system.run(17, 61)?
+        .ok_or_else(|| Error::solve("No bot ever compares chips 17 and 61."))?;
+    Ok(Answer::from(bot))
+}
+
+pub fn part2(system: &balance_bots::System) -> Result<Answer, Error> {
+    let mut system = system.clone();
+    system.run(-1, -1)?;
+    Ok(Answer::from(system.get_output(0)? * system.get_output(1)? * system.get_output(2)?))
 }
 
 mod balance_bots {
     use std::collections::HashMap;
     use lazy_static::lazy_static;
-    use regex::Regex;
+    use regex::{Captures, Regex};
+
+    use crate::captures::{group, group_str};
+    use crate::error::Error;
     use std::cmp::{min, max};
 
     lazy_static! {
@@ -30,16 +40,22 @@ mod balance_bots {
         Output(i32)
     }
 
-    fn type_and_id_to_destination(dest_type: &str, dest_id: i32) -> Destination {
+    fn type_and_id_to_destination(dest_type: &str, dest_id: i32) -> Result<Destination, Error> {
         match dest_type {
-            "bot" => Destination::Bot(dest_id),
-            "output" => Destination::Output(dest_id),
-            _ => {
-                panic!("Unsupported destination type: {}", dest_type)
-            }
+            "bot" => Ok(Destination::Bot(dest_id)),
+            "output" => Ok(Destination::Output(dest_id)),
+            _ => Err(Error::parse(format!("Unsupported destination type: {}", dest_type)))
         }
     }
 
+    /// The bot a rule is for, and where that bot passes its chips.
+    fn bot_from_captures(captures: &Captures) -> Result<(i32, Bot), Error> {
+        let bot_id: i32 = group(captures, 1)?;
+        let low_dest = type_and_id_to_destination(group_str(captures, 2)?, group(captures, 3)?)?;
+        let high_dest = type_and_id_to_destination(group_str(captures, 4)?, group(captures, 5)?)?;
+        Ok((bot_id, Bot::new(low_dest, high_dest)))
+    }
+
     #[derive (Clone)]
     struct Bot {
         chips: Vec<i32>,
@@ -57,11 +73,12 @@ mod balance_bots {
             }
         }
 
-        pub fn add_chip(&mut self, chip: i32) {
+        pub fn add_chip(&mut self, chip: i32) -> Result<(), Error> {
             if self.chips.len() >= 2 {
-                panic!("Tried to give bot more than two chips.");
+                return Err(Error::solve("Tried to give bot more than two chips."));
             }
             self.chips.push(chip);
+            Ok(())
         }
 
         pub fn ready_to_proceed(&self) -> bool {
@@ -70,6 +87,7 @@ mod balance_bots {
 
     }
 
+    #[derive (Clone)]
     pub struct System {
         bots: HashMap<i32, Bot>,
         outputs: HashMap<i32, i32>
@@ -84,60 +102,55 @@ mod balance_bots {
             }
         }
 
-        pub fn new_from_rules(rules: &Vec<String>) -> System {
+        pub fn new_from_rules(rules: &[String]) -> Result<System, Error> {
             
             let mut system = System::new();
 
             // parse all of the bot rules first so we can initialise them
-            for rule in rules {
-                
+            for (i, rule) in rules.iter().enumerate() {
                 if let Some(captures) = BOT_RULE_REGEX.captures(rule) {
-
-                    // Extract values from the regex captures
-                    let bot_id = captures.get(1).unwrap().as_str();
-                    let bot_id: i32 = bot_id.parse().expect("Bot ID value not an integer.");
-                    let low_dest_type = captures.get(2).unwrap().as_str();
-                    let low_dest_id = captures.get(3).unwrap().as_str();
-                    let low_dest_id: i32 = low_dest_id.parse().expect("Lower destination ID value not an integer.");
-                    let high_dest_type = captures.get(4).unwrap().as_str();
-                    let high_dest_id = captures.get(5).unwrap().as_str();
-                    let high_dest_id: i32 = high_dest_id.parse().expect("Upper destination ID value not an integer.");
-                    let low_dest = type_and_id_to_destination(low_dest_type, low_dest_id);
-                    let high_dest = type_and_id_to_destination(high_dest_type, high_dest_id);
-
-                    // Create any new outputs in the system
-                    if let Destination::Output(id) = low_dest {
-                        system.outputs.insert(id, -1);
+                    let (bot_id, bot) = bot_from_captures(&captures)
+                        .map_err(|e| e.at_line(i + 1, rule))?;
+                    if system.bots.insert(bot_id, bot).is_some() {
+                        return Err(Error::parse(format!("Bot {} already has a rule.", bot_id)).at_line(i + 1, rule));
                     }
-                    if let Destination::Output(id) = high_dest {
-                        system.outputs.insert(id, -1);
+                } else if !CHIP_START_REGEX.is_match(rule) {
+                    return Err(Error::parse("Expected a bot rule or a chip going to a bot.").at_line(i + 1, rule));
+                }
+            }
+
+            // every bot that chips are passed to needs a rule of its own
+            for (bot_id, bot) in system.bots.iter() {
+                for dest in [&bot.low_dest, &bot.high_dest] {
+                    if let Destination::Bot(id) = dest {
+                        if !system.bots.contains_key(id) {
+                            return Err(Error::parse(format!("Bot {} passes chips to bot {}, which has no rule.", bot_id, id)));
+                        }
                     }
-                    
-                    // Create the bot and add it to the system
-                    let bot = Bot::new(low_dest, high_dest);
-                    system.bots.insert(bot_id, bot);
                 }
             }
             
             // now parse all of the initialisation rules
-            for rule in rules {
-                
+            for (i, rule) in rules.iter().enumerate() {
                 if let Some(captures) = CHIP_START_REGEX.captures(rule) {
 
                     // Extract values from the regex captures
-                    let chip = captures.get(1).unwrap().as_str();
-                    let chip: i32 = chip.parse().expect("Chip value not an integer.");
-                    let bot_id = captures.get(2).unwrap().as_str();
-                    let bot_id: i32 = bot_id.parse().expect("Bot ID value not an integer.");
+                    let chip: i32 = group(&captures, 1).map_err(|e| e.at_line(i + 1, rule))?;
+                    let bot_id: i32 = group(&captures, 2).map_err(|e| e.at_line(i + 1, rule))?;
 
                     // Add the chip into the system
-                    system.bots.get_mut(&bot_id).expect("Invalid bot ID when trying to assign chip.").add_chip(chip);
+                    system.bots.get_mut(&bot_id)
+                        .ok_or_else(|| Error::parse(format!("Invalid bot ID {} when trying to assign chip.", bot_id)))
+                        .and_then(|bot| bot.add_chip(chip).map_err(|e| Error::parse(e.message)))
+                        .map_err(|e| e.at_line(i + 1, rule))?;
                 }
             }
-            system
+            Ok(system)
         }
 
-        pub fn run(&mut self, stop_low: i32, stop_high: i32) -> i32 {
+        /// Pass chips around until no bot can go on, or until a bot compares
+        /// the two stop chips, giving that bot's ID.
+        pub fn run(&mut self, stop_low: i32, stop_high: i32) -> Result<Option<i32>, Error> {
             // On each pass of the loop, check for bots that are ready to proceed and resolve them.
             let mut changed_state = true;
             while changed_state {
@@ -149,15 +162,15 @@ mod balance_bots {
                         let high_chip = max(bot.chips[0], bot.chips[1]);
 
                         if low_chip == stop_low && high_chip == stop_high {
-                            return *bot_id;
+                            return Ok(Some(*bot_id));
                         }
 
                         match bot.low_dest {
                             Destination::Bot(id) => {
                                 new_bots
                                     .get_mut(&id)
-                                    .expect("Invalid bot ID in rule")
-                                    .add_chip(low_chip);
+                                    .expect("Destinations are checked when parsing.")
+                                    .add_chip(low_chip)?;
                             },
                             Destination::Output(id) => {
                                 self.outputs.insert(id, low_chip);
@@ -167,8 +180,8 @@ mod balance_bots {
                             Destination::Bot(id) => {
                                 new_bots
                                     .get_mut(&id)
-                                    .expect("Invalid bot ID in rule")
-                                    .add_chip(high_chip);
+                                    .expect("Destinations are checked when parsing.")
+                                    .add_chip(high_chip)?;
                             },
                             Destination::Output(id) => {
                                 self.outputs.insert(id, high_chip);
@@ -181,11 +194,13 @@ mod balance_bots {
                 }
                 self.bots = new_bots;
             }
-            return -1;
+            Ok(None)
         }
 
-        pub fn get_output(&self, id: i32) -> i32 {
-            *self.outputs.get(&id).expect("Asked for non-existent output.")
+        pub fn get_output(&self, id: i32) -> Result<i32, Error> {
+            self.outputs.get(&id)
+                .copied()
+                .ok_or_else(|| Error::solve(format!("Output {} never got a chip.", id)))
         }
     }
 
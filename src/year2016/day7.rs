@@ -1,16 +1,26 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
+
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+  parse_each_line(input, |line| {
+    if line.chars().all(|c| c.is_ascii_lowercase() || c == '[' || c == ']') {
+      Ok(line.to_string())
+    } else {
+      Err(Error::parse("An IPv7 address is made of lowercase letters and square brackets."))
+    }
+  })
+}
 
-pub fn part1(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
   // Get the amount of lines that support TLS
   let amount = lines.iter().map(|ipv7| ipv7::supports_tls(ipv7) as u32).sum::<u32>();
-  println!("{}", amount);
+  Ok(Answer::from(amount))
 }
 
-pub fn part2(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
   let amount = lines.iter().map(|ipv7| ipv7::supports_ssl(ipv7) as u32).sum::<u32>();
-  println!("{}", amount);
+  Ok(Answer::from(amount))
 }
 
 mod ipv7 {
@@ -24,7 +34,7 @@ mod ipv7 {
         let mut result = false;
         // Assumes ASCII
         let line_bytes = ipv7.as_bytes();
-        for i in 0 .. ipv7.len() - 3 {
+        for i in 0 .. ipv7.len().saturating_sub(3) {
             let c = line_bytes[i] as char;
             // Keep track of whether cursor is inside or outside
             // square brackets
@@ -109,7 +119,7 @@ mod ipv7 {
         let mut babs = Vec::<[char; 3]>::new();
         // Assumes ASCII
         let line_bytes = ipv7.as_bytes();
-        for i in 0 .. ipv7.len() - 2 {
+        for i in 0 .. ipv7.len().saturating_sub(2) {
             let c = line_bytes[i] as char;
             // Keep track of whether cursor is inside or outside
             // square brackets
@@ -1,79 +1,67 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::captures::named;
+use crate::answer::Answer;
+use crate::error::Error;
 
-use regex::{Regex, Captures};
+use lazy_static::lazy_static;
+use regex::Regex;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let commands = parse_lines(&lines);
-    let mut screen = screen::Screen::new(50, 6);
+const WIDTH: usize = 50;
+const HEIGHT: usize = 6;
+
+pub fn parse(input: &PuzzleInput) -> Result<Vec<screen::Command>, Error> {
+    parse_each_line(input, parse_command)
+}
+
+pub fn part1(commands: &[screen::Command]) -> Result<Answer, Error> {
+    let mut screen = screen::Screen::new(WIDTH, HEIGHT);
     for command in commands {
-        screen = screen.do_command(command)
+        screen = screen.do_command(*command)
     }
-    println!("{}", screen.count_on())
+    Ok(Answer::from(screen.count_on()))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let commands = parse_lines(&lines);
-    let mut screen = screen::Screen::new(50, 6);
+pub fn part2(commands: &[screen::Command]) -> Result<Answer, Error> {
+    let mut screen = screen::Screen::new(WIDTH, HEIGHT);
     for command in commands {
-        screen = screen.do_command(command)
+        screen = screen.do_command(*command)
     }
-    println!("{}", screen)
-
+    Ok(Answer::from(screen.to_string().trim_end()))
 }
 
-fn parse_usize_from_regex_capture(captures: &Captures, capture_name: &str) -> usize {
-    let value =
-        captures
-            .name(capture_name)
-            .unwrap()
-            .as_str();
-    value.parse().expect(format!("Invalid {}: {}", capture_name, value).as_str())
-}
+fn parse_command(line: &str) -> Result<screen::Command, Error> {
+    lazy_static! {
+        static ref RECT_REGEX: Regex = Regex::new(r"rect (?P<width>\d+)x(?P<height>\d+)").unwrap();
+        static ref ROTATE_ROW_REGEX: Regex = Regex::new(r"rotate row y=(?P<row_y>\d+) by (?P<amount>\d+)").unwrap();
+        static ref ROTATE_COL_REGEX: Regex = Regex::new(r"rotate column x=(?P<col_x>\d+) by (?P<amount>\d+)").unwrap();
+    }
 
-fn parse_lines(lines: &Vec<String>) -> Vec<screen::Command> {
-    let rect_regex = Regex::new(r"rect (?P<width>\d+)x(?P<height>\d+)").unwrap();
-    let rotate_row_regex = Regex::new(r"rotate row y=(?P<row_y>\d+) by (?P<amount>\d+)").unwrap();
-    let rotate_col_regex = Regex::new(r"rotate column x=(?P<col_x>\d+) by (?P<amount>\d+)").unwrap();
-
-    let mut commands = Vec::<screen::Command>::new();
-    for line in lines {
-        match rect_regex.captures(&line) {
-            Some(captures) => {
-                let width = parse_usize_from_regex_capture(&captures, "width");
-                let height = parse_usize_from_regex_capture(&captures, "height");
-                commands.push(screen::Command::Rect(width, height));
-                continue;
-            },
-            None => ()
+    if let Some(captures) = RECT_REGEX.captures(line) {
+        let (width, height) = (named(&captures, "width")?, named(&captures, "height")?);
+        if width > WIDTH || height > HEIGHT {
+            return Err(Error::parse(format!("A {}x{} rectangle doesn't fit on the {}x{} screen.", width, height, WIDTH, HEIGHT)));
         }
-        match rotate_row_regex.captures(&line) {
-            Some(captures) => {
-                let row_y = parse_usize_from_regex_capture(&captures, "row_y");
-                let amount = parse_usize_from_regex_capture(&captures, "amount");
-                commands.push(screen::Command::RotateRow(row_y, amount));
-                continue;
-            },
-            None => ()
+        Ok(screen::Command::Rect(width, height))
+    } else if let Some(captures) = ROTATE_ROW_REGEX.captures(line) {
+        let row_y = named(&captures, "row_y")?;
+        if row_y >= HEIGHT {
+            return Err(Error::parse(format!("There is no row {} on a screen {} high.", row_y, HEIGHT)));
         }
-        match rotate_col_regex.captures(&line) {
-            Some(captures) => {
-                let col_x = parse_usize_from_regex_capture(&captures, "col_x");
-                let amount = parse_usize_from_regex_capture(&captures, "amount");
-                commands.push(screen::Command::RotateCol(col_x, amount));
-            },
-            None => {
-                panic!("This line of input is not of any accepted pattern: {}", &line)
-            }
+        Ok(screen::Command::RotateRow(row_y, named(&captures, "amount")?))
+    } else if let Some(captures) = ROTATE_COL_REGEX.captures(line) {
+        let col_x = named(&captures, "col_x")?;
+        if col_x >= WIDTH {
+            return Err(Error::parse(format!("There is no column {} on a screen {} wide.", col_x, WIDTH)));
         }
+        Ok(screen::Command::RotateCol(col_x, named(&captures, "amount")?))
+    } else {
+        Err(Error::parse("This line of input is not of any accepted pattern."))
     }
-    commands
 }
 
 mod screen {
 
-    use crate::data_structs::Grid;
+    use crate::data_structs::{Coord, Grid};
     
     use std::fmt;
 
@@ -82,6 +70,7 @@ mod screen {
         pixels: Grid<bool>,
     }
 
+    #[derive(Clone, Copy)]
     pub enum Command {
         Rect(usize, usize),
         RotateRow(usize, usize),
@@ -102,60 +91,33 @@ mod screen {
             }
         }
 
-        pub fn width(&self) -> usize {
-            return self.pixels.xsize
-        }
-
-        pub fn height(&self) -> usize {
-            return self.pixels.ysize
-        }
-
         pub fn count_on(&self) -> usize {
             self.pixels.into_iter().map(|pixel| if *pixel {1} else {0}).sum()
         }
 
         fn draw_rect_top_left(&self, width: usize, height: usize) -> Screen {
             let mut screen_prime = self.clone();
-            for x in 0..width {
-                for y in 0..height {
-                    screen_prime.pixels.set(x, y, true);
-                }
-            }
+            screen_prime.pixels.splice(&Grid::new(width, height, true), Coord { x: 0, y: 0 });
             screen_prime
         }
 
         fn rotate_row(&self, y: usize, offset: usize) -> Screen {
             let mut screen_prime = self.clone();
-            for x in 0..self.width() {
-                let new_x = (x + offset) % self.width();
-                let val = self.pixels.get(x, y);
-                screen_prime.pixels.set(new_x, y, *val);
-            }
+            screen_prime.pixels.rotate_row(y, offset);
             screen_prime
         }
 
         fn rotate_column(&self, x: usize, offset: usize) -> Screen {
             let mut screen_prime = self.clone();
-            for y in 0..self.height() {
-                let new_y = (y + offset) % self.height();
-                let val = self.pixels.get(x, y);
-                screen_prime.pixels.set(x, new_y, *val);
-            }
-            screen_prime            
+            screen_prime.pixels.rotate_col(x, offset);
+            screen_prime
         }
 
     }
 
     impl fmt::Display for Screen {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            let mut repr = "".to_string();
-            for y in 0..self.height(){
-                for x in 0..self.width() {
-                    repr.push_str(if *self.pixels.get(x, y) { "#" } else { "." })
-                }
-                repr.push_str("\n")
-            }
-            write!(f, "{}", repr)
+            writeln!(f, "{}", self.pixels.render(|on| if *on { '#' } else { '.' }))
         }
     }
 
@@ -1,27 +1,20 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let readings = parse_lines(lines);
-    let increasing = no_of_increasing_readings(readings);
-    println!("{}", increasing);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<u32>, Error> {
+    parse_lines(input)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let readings = parse_lines(lines);
-    let sums = sums_of_three(readings);
-    let increasing = no_of_increasing_readings(sums);
-    println!("{}", increasing);
+pub fn part1(readings: &[u32]) -> Result<Answer, Error> {
+    let increasing = no_of_increasing_readings(readings.to_vec());
+    Ok(Answer::from(increasing))
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<u32> {
-    let mut readings = Vec::<u32>::new();
-    for line in lines {
-        let reading = line.parse::<u32>().expect("Could not parse number in input file.");
-        readings.push(reading);
-    }
-    readings
+pub fn part2(readings: &[u32]) -> Result<Answer, Error> {
+    let sums = sums_of_three(readings.to_vec());
+    let increasing = no_of_increasing_readings(sums);
+    Ok(Answer::from(increasing))
 }
 
 fn no_of_increasing_readings(readings: Vec<u32>) -> u32 {
@@ -1,15 +1,24 @@
 use itertools::Itertools;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    parse_each_line(input, |line| {
+        match line.chars().find(|c| !"()[]{}<>".contains(*c)) {
+            Some(c) => Err(Error::parse(format!("Illegal character {} found.", c))),
+            None => Ok(line.to_string())
+        }
+    })
+}
+
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
     let score: u32 = lines.iter().map(|l| corruption_score(l)).sum();
-    println!("{}", score);
+    Ok(Answer::from(score))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
     let scores = lines.iter().map(
         |l| autocomplete_score(l)
     ).filter(
@@ -17,8 +26,11 @@ pub fn part2(input_file_path: &str) {
     ).map(
         |o| o.unwrap()
     ).sorted().collect::<Vec<u64>>();
+    if scores.is_empty() {
+        return Err(Error::solve("Every line is corrupted."));
+    }
     let middle_index = scores.len() / 2;
-    println!("{}", scores[middle_index]);
+    Ok(Answer::from(scores[middle_index]))
 }
 
 fn corruption_score(line: &str) -> u32 {
@@ -45,7 +57,7 @@ fn corruption_score(line: &str) -> u32 {
                 None => break,
                 Some(d) => if d != '<' { return 25137 }
             },
-            _ => panic!("Illegal character found")
+            _ => unreachable!("Characters are checked when parsing.")
         }
     }
     0
@@ -75,7 +87,7 @@ fn autocomplete_score(line: &str) -> Option<u64> {
                 None => break,
                 Some(d) => if d != '<' { return None }
             },
-            _ => panic!("Illegal character found")
+            _ => unreachable!("Characters are checked when parsing.")
         }
     }
     stack.reverse();
@@ -87,7 +99,7 @@ fn autocomplete_score(line: &str) -> Option<u64> {
             '[' => 2,
             '{' => 3,
             '<' => 4,
-            _ => panic!("Illegal character found")
+            _ => unreachable!("Characters are checked when parsing.")
         }
     }
     Some(score)
@@ -1,25 +1,24 @@
-use crate::{input_file::read_lines, data_structs::Grid};
+use crate::{input_file::{read_lines, PuzzleInput}, data_structs::{Coord, Edges, Grid, SURROUNDING}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let octopuses = parse_lines(lines);
-    let flashes = simulate_octopuses(&octopuses, 100);
-    println!("{}", flashes);
+pub fn parse(input: &PuzzleInput) -> Result<Grid<u8>, Error> {
+    let grid = Grid::from_digits(&read_lines(input)?)?;
+    if grid.xsize != 10 || grid.ysize != 10 {
+        return Err(Error::parse("Expected a 10x10 grid of octopuses."));
+    }
+    Ok(grid)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let octopuses = parse_lines(lines);
-    let step = get_simultaneous_flash(&octopuses);
-    println!("{}", step);
+pub fn part1(octopuses: &Grid<u8>) -> Result<Answer, Error> {
+    let flashes = simulate_octopuses(octopuses, 100);
+    Ok(Answer::from(flashes))
 }
 
-fn parse_lines(lines: Vec<String>) -> Grid<u8> {
-    let elements = lines.concat().chars().map(
-        |c|
-        c.to_digit(10).unwrap_or_else(|| panic!("Unable to parse character to int: {}", c)) as u8
-    ).collect();
-    Grid::<u8>::with_elements(lines[0].len(), lines.len(), elements)
+pub fn part2(octopuses: &Grid<u8>) -> Result<Answer, Error> {
+    let step = get_simultaneous_flash(octopuses)
+        .ok_or_else(|| Error::solve("The octopuses never all flash at once."))?;
+    Ok(Answer::from(step))
 }
 
 fn simulate_octopuses(octopuses: &Grid<u8>, steps: u32) -> u32 {
@@ -44,14 +43,10 @@ fn simulate_octopuses(octopuses: &Grid<u8>, steps: u32) -> u32 {
                         modified = true;
                         flashes += 1;
                         *octopus += 1;
-                        for x1 in x.saturating_sub(1) .. x+2 {
-                            for y1 in y.saturating_sub(1) .. y+2 {
-                                if x1 < 10 && y1 < 10 && !(x == x1 && y == y1) {
-                                    let neighbour = octopuses.get_mut(x1, y1);
-                                    if *neighbour < 10 {
-                                        *neighbour += 1
-                                    }
-                                }
+                        for Coord {x: x1, y: y1} in octopuses.neighbour_coords(x, y, &SURROUNDING, Edges::Bounded) {
+                            let neighbour = octopuses.get_mut(x1, y1);
+                            if *neighbour < 10 {
+                                *neighbour += 1
                             }
                         }
                     }
@@ -71,7 +66,7 @@ fn simulate_octopuses(octopuses: &Grid<u8>, steps: u32) -> u32 {
     flashes
 }
 
-fn get_simultaneous_flash(octopuses: &Grid<u8>) -> u32 {
+fn get_simultaneous_flash(octopuses: &Grid<u8>) -> Option<u32> {
     let mut octopuses = octopuses.clone();
     for i in 1..1000000 {
         let mut flashes = 0;
@@ -93,14 +88,10 @@ fn get_simultaneous_flash(octopuses: &Grid<u8>) -> u32 {
                         modified = true;
                         flashes += 1;
                         *octopus += 1;
-                        for x1 in x.saturating_sub(1) .. x+2 {
-                            for y1 in y.saturating_sub(1) .. y+2 {
-                                if x1 < 10 && y1 < 10 && !(x == x1 && y == y1) {
-                                    let neighbour = octopuses.get_mut(x1, y1);
-                                    if *neighbour < 10 {
-                                        *neighbour += 1
-                                    }
-                                }
+                        for Coord {x: x1, y: y1} in octopuses.neighbour_coords(x, y, &SURROUNDING, Edges::Bounded) {
+                            let neighbour = octopuses.get_mut(x1, y1);
+                            if *neighbour < 10 {
+                                *neighbour += 1
                             }
                         }
                     }
@@ -109,7 +100,7 @@ fn get_simultaneous_flash(octopuses: &Grid<u8>) -> u32 {
         }
         // Check if everyone flashed
         if flashes == 100 {
-            return i;
+            return Some(i);
         }
         // Reset flashers to 0
         for x in 0..10 {
@@ -121,5 +112,5 @@ fn get_simultaneous_flash(octopuses: &Grid<u8>) -> u32 {
             }
         }
     }
-    0
+    None
 }
\ No newline at end of file
@@ -1,25 +1,30 @@
-use crate::{input_file::read_lines};
+use crate::{input_file::{read_lines, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
 use self::cave_system::CaveSystem;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let system = CaveSystem::from_string_repr(lines);
-    let path_count = cave_system::count_paths(&system, false);
-    println!("{}", path_count);
+pub fn parse(input: &PuzzleInput) -> Result<CaveSystem, Error> {
+    let lines = read_lines(input)?;
+    CaveSystem::from_string_repr(lines)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let system = CaveSystem::from_string_repr(lines);
-    let path_count = cave_system::count_paths(&system, true);
-    println!("{}", path_count);
+pub fn part1(system: &CaveSystem) -> Result<Answer, Error> {
+    let path_count = cave_system::count_paths(system, false);
+    Ok(Answer::from(path_count))
+}
+
+pub fn part2(system: &CaveSystem) -> Result<Answer, Error> {
+    let path_count = cave_system::count_paths(system, true);
+    Ok(Answer::from(path_count))
 }
 
 mod cave_system {
     use std::collections::HashMap;
     use regex::Regex;
 
+    use crate::error::Error;
+
     #[derive(Clone)]
     pub struct CaveSystem {
         caves: HashMap<String, Cave>,
@@ -40,23 +45,34 @@ mod cave_system {
     }
 
     impl CaveSystem {
-        pub fn from_string_repr(lines: Vec<String>) -> CaveSystem {
+        pub fn from_string_repr(lines: Vec<String>) -> Result<CaveSystem, Error> {
             let mut caves_by_id = HashMap::<String, Cave>::new();
-            for line in lines {
+            for (i, line) in lines.iter().enumerate() {
                 let cave_ids = line.split('-').collect::<Vec<&str>>();
+                let named = |id: &&str| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphabetic());
+                if cave_ids.len() != 2 || !cave_ids.iter().all(named) {
+                    return Err(Error::parse("Expected two cave names joined by -.").at_line(i + 1, line));
+                }
                 // Add caves that haven't been seen yet
                 for cave_id in cave_ids.iter() {
                     if !caves_by_id.contains_key(*cave_id) {
                         caves_by_id.insert(cave_id.to_string(), Cave::new(cave_id));
                     }
                 }
+                // Two large caves next to each other could be walked between forever
+                if cave_ids.iter().all(|id| caves_by_id[*id].cave_type == CaveType::Large) {
+                    return Err(Error::parse("Two large caves can't be connected.").at_line(i + 1, line));
+                }
                 // Connect the caves
                 let cave_a = caves_by_id.get_mut(cave_ids[0]).unwrap();
                 cave_a.connections.push(cave_ids[1].to_string());
                 let cave_b = caves_by_id.get_mut(cave_ids[1]).unwrap();
                 cave_b.connections.push(cave_ids[0].to_string());
             }
-            CaveSystem { caves: caves_by_id, visited_one_twice: false }
+            if !caves_by_id.contains_key("start") {
+                return Err(Error::parse("There is no start cave."));
+            }
+            Ok(CaveSystem { caves: caves_by_id, visited_one_twice: false })
         }
     }
 
@@ -1,52 +1,50 @@
 use std::collections::HashSet;
 
-use itertools::Itertools;
+use crate::{input_file::{parse_each, parse_two_sections, parse_value, split_parse, PuzzleInput}, data_structs::{Coord, Grid}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-use crate::{input_file::read_lines, data_structs::{Coord, Grid}};
+pub fn parse(input: &PuzzleInput) -> Result<(HashSet<Coord>, Vec<Fold>), Error> {
+    parse_two_sections(
+        input,
+        |lines| Ok(parse_each(lines, parse_dot)?.into_iter().collect()),
+        |lines| parse_each(lines, parse_fold)
+    )
+}
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (dots, folds) = parse_lines(lines);
-    let dots = do_fold(dots, &folds[0]);
-    println!("{}", dots.len());
+pub fn part1((dots, folds): &(HashSet<Coord>, Vec<Fold>)) -> Result<Answer, Error> {
+    let first = folds.first().ok_or_else(|| Error::solve("There are no folds."))?;
+    let dots = do_fold(dots.clone(), first)?;
+    Ok(Answer::from(dots.len()))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (mut dots, folds) = parse_lines(lines);
+pub fn part2((dots, folds): &(HashSet<Coord>, Vec<Fold>)) -> Result<Answer, Error> {
+    let mut dots = dots.clone();
     for fold in folds {
-        dots = do_fold(dots, &fold);
+        dots = do_fold(dots, fold)?;
     }
-    print_dots(&dots);
+    Ok(Answer::from(draw_dots(&dots)))
 }
 
-fn parse_lines(lines: Vec<String>) -> (HashSet<Coord>, Vec<Fold>) {
-    let mut on_folds = false;
-    let mut dots = HashSet::<Coord>::new();
-    let mut folds = Vec::<Fold>::new();
-    for line in lines {
-        if line == "" {
-            on_folds = true;
-            continue;
-        }
-        if !on_folds {
-            let parts: Vec<usize> = line.split(',').map(|n| n.parse().expect("Coord digit couldn't be parsed.")).collect();
-            dots.insert(Coord {x: parts[0], y: parts[1]});
-        } else {
-            let parts: Vec<&str> = line.split('=').collect();
-            let position = parts[1].parse::<usize>().expect("Could not parse fold axis.");
-            let axis = match parts[0] {
-                "fold along x" => FoldAxis::X,
-                "fold along y" => FoldAxis::Y,
-                _ => panic!("Invalid fold.")
-            };
-            folds.push(Fold {axis, position});
-        }
+fn parse_dot(line: &str) -> Result<Coord, Error> {
+    match split_parse::<usize>(line, ",")?[..] {
+        [x, y] => Ok(Coord {x, y}),
+        _ => Err(Error::parse("Expected a dot at x,y."))
     }
-    (dots, folds)
 }
 
-struct Fold {
+fn parse_fold(line: &str) -> Result<Fold, Error> {
+    let (axis, position) = line.split_once('=')
+        .ok_or_else(|| Error::parse("Expected a fold along x=N or y=N."))?;
+    let axis = match axis {
+        "fold along x" => FoldAxis::X,
+        "fold along y" => FoldAxis::Y,
+        _ => return Err(Error::parse("Invalid fold."))
+    };
+    Ok(Fold {axis, position: parse_value(position)?})
+}
+
+pub struct Fold {
     axis: FoldAxis,
     position: usize
 }
@@ -56,14 +54,14 @@ enum FoldAxis {
     Y
 }
 
-fn do_fold(dots: HashSet<Coord>, fold: &Fold) -> HashSet<Coord> {
+fn do_fold(dots: HashSet<Coord>, fold: &Fold) -> Result<HashSet<Coord>, Error> {
     let mut new_dots = dots.clone();
     match fold.axis {
         FoldAxis::X => {
             for dot in dots {
                 if dot.x > fold.position {
                     new_dots.remove(&dot);
-                    let new_x = fold.position - (dot.x - fold.position);
+                    let new_x = reflect(dot.x, fold.position)?;
                     new_dots.insert(Coord {x: new_x, y: dot.y});
                 }
             }
@@ -72,25 +70,28 @@ fn do_fold(dots: HashSet<Coord>, fold: &Fold) -> HashSet<Coord> {
             for dot in dots {
                 if dot.y > fold.position {
                     new_dots.remove(&dot);
-                    let new_y = fold.position - (dot.y - fold.position);
+                    let new_y = reflect(dot.y, fold.position)?;
                     new_dots.insert(Coord {x: dot.x, y: new_y});
                 }
             }
         }
     }
-    new_dots
+    Ok(new_dots)
+}
+
+/// Where a dot past the fold lands, unless it's so far past that it
+/// would land off the paper.
+fn reflect(value: usize, position: usize) -> Result<usize, Error> {
+    position.checked_sub(value - position)
+        .ok_or_else(|| Error::solve(format!("A dot at {} folds off the paper when folding along {}.", value, position)))
 }
 
-fn print_dots(dots: &HashSet<Coord>) {
-    let max_x = dots.iter().max_by(|a, b| a.x.cmp(&b.x)).unwrap().x;
-    let max_y = dots.iter().max_by(|a, b| a.y.cmp(&b.y)).unwrap().y;
+fn draw_dots(dots: &HashSet<Coord>) -> String {
+    let max_x = dots.iter().map(|dot| dot.x).max().unwrap_or(0);
+    let max_y = dots.iter().map(|dot| dot.y).max().unwrap_or(0);
     let mut grid = Grid::<char>::new(max_x + 1, max_y + 1, '.');
     for dot in dots {
         grid.set(dot.x, dot.y, '#');
     }
-    for y in 0 .. grid.ysize {
-        let row = 
-            (0 .. grid.xsize).map(|x| grid.get(x, y)).join("");
-        println!("{}", row);
-    }
+    grid.to_string()
 }
\ No newline at end of file
@@ -1,41 +1,50 @@
 use std::collections::HashMap;
 
-use crate::{input_file::read_lines};
+use crate::{input_file::{parse_each, parse_two_sections, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    do_part(input_file_path, 10);
+/// The polymer template and the pair insertion rules.
+type Manual = (String, HashMap<(char, char), char>);
+
+pub fn parse(input: &PuzzleInput) -> Result<Manual, Error> {
+    let (polymer, rules) = parse_two_sections(input, parse_template, |lines| parse_each(lines, parse_rule))?;
+    Ok((polymer, rules.into_iter().collect()))
+}
+
+pub fn part1((polymer, rules): &Manual) -> Result<Answer, Error> {
+    Ok(Answer::from(do_part(polymer, rules, 10)))
 }
 
-pub fn part2(input_file_path: &str) {
-    do_part(input_file_path, 40);
+pub fn part2((polymer, rules): &Manual) -> Result<Answer, Error> {
+    Ok(Answer::from(do_part(polymer, rules, 40)))
 }
 
-fn do_part(input_file_path: &str, iterations: u32) {
-    let lines = read_lines(input_file_path);
-    let (polymer, rules) = parse_lines(lines);
+fn do_part(polymer: &str, rules: &HashMap<(char, char), char>, iterations: u32) -> u64 {
     let first = polymer.chars().next().unwrap();
     let last = polymer.chars().rev().next().unwrap();
-    let mut polymer = string_polymer_to_pair_map(&polymer);
+    let mut polymer = string_polymer_to_pair_map(polymer);
     for _ in 0..iterations {
-        polymer = polymerize(&polymer, &rules);
+        polymer = polymerize(&polymer, rules);
     }
     let (least_common_count, most_common_count) = 
         least_and_most_common_element_count(&polymer, first, last);
-    println!("{}", most_common_count - least_common_count);
+    most_common_count - least_common_count
+}
+
+fn parse_template(lines: &[String]) -> Result<String, Error> {
+    match lines {
+        [polymer] if polymer.len() >= 2 && polymer.chars().all(|c| c.is_ascii_uppercase()) => Ok(polymer.clone()),
+        _ => Err(Error::parse("Expected a polymer template of at least two elements."))
+    }
 }
 
-fn parse_lines(lines: Vec<String>) -> (String, HashMap<(char, char), char>) {
-    let polymer = lines[0].clone();
-    let mut rules = HashMap::<(char, char), char>::new();
-    for i in 2 .. lines.len() {
-        let line = &lines[i];
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        let key: Vec<char> = parts[0].chars().collect();
-        let key = (key[0], key[1]);
-        let val = parts[1].chars().next().unwrap();
-        rules.insert(key, val);
+fn parse_rule(line: &str) -> Result<((char, char), char), Error> {
+    let parts: Vec<Vec<char>> = line.split(" -> ").map(|part| part.chars().collect()).collect();
+    match &parts[..] {
+        [key, val] if key.len() == 2 && val.len() == 1 => Ok(((key[0], key[1]), val[0])),
+        _ => Err(Error::parse("Expected a pair insertion rule like AB -> C."))
     }
-    (polymer, rules)
 }
 
 // fn polymerize(polymer: &str, rules: &HashMap<(char, char), char>) -> String {
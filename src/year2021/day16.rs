@@ -1,49 +1,45 @@
 use crate::{
-    input_file::read_all_to_string,
+    input_file::{read_all_to_string, PuzzleInput},
     binary::bin_to_dec
 };
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let binary = to_binary(&input);
-    let (packets, _) = parse_packets(&binary, Some(1));
-    let version_sum = sum_version_nums(&packets[0]);
-    println!("{}", version_sum);
+pub fn parse(input: &PuzzleInput) -> Result<Packet, Error> {
+    let binary = to_binary(read_all_to_string(input)?.trim())?;
+    let (mut packets, _) = parse_packets(&binary, Some(1))?;
+    packets.pop().ok_or_else(|| Error::parse("The transmission doesn't contain a packet."))
 }
 
-pub fn part2(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let binary = to_binary(&input);
-    let (packets, _) = parse_packets(&binary, Some(1));
-    let value = eval_packet(&packets[0]);
-    println!("{}", value);
+pub fn part1(packet: &Packet) -> Result<Answer, Error> {
+    let version_sum = sum_version_nums(packet);
+    Ok(Answer::from(version_sum))
 }
 
-fn to_binary(hex: &str) -> Vec<bool> {
-    hex.chars().map(|c| {
-        match c {
-            '0' => [false, false, false, false],
-            '1' => [false, false, false, true],
-            '2' => [false, false, true, false],
-            '3' => [false, false, true, true],
-            '4' => [false, true, false, false],
-            '5' => [false, true, false, true],
-            '6' => [false, true, true, false],
-            '7' => [false, true, true, true],
-            '8' => [true, false, false, false],
-            '9' => [true, false, false, true],
-            'A' => [true, false, true, false],
-            'B' => [true, false, true, true],
-            'C' => [true, true, false, false],
-            'D' => [true, true, false, true],
-            'E' => [true, true, true, false],
-            'F' => [true, true, true, true],
-            _ => panic!("Invalid hex character")
-        }
-    }).flatten().collect()
+pub fn part2(packet: &Packet) -> Result<Answer, Error> {
+    let value = eval_packet(packet);
+    Ok(Answer::from(value))
+}
+
+fn to_binary(hex: &str) -> Result<Vec<bool>, Error> {
+    let mut binary = Vec::<bool>::new();
+    for c in hex.chars() {
+        let digit = c.to_digit(16)
+            .filter(|_| !c.is_ascii_lowercase())
+            .ok_or_else(|| Error::parse(format!("Invalid hex character {}.", c)))?;
+        binary.extend((0..4).rev().map(|bit| digit & (1 << bit) != 0));
+    }
+    Ok(binary)
+}
+
+/// The bits in the range, failing if the transmission
+/// ends before they do.
+fn bits(binary: &[bool], start: usize, end: usize) -> Result<&[bool], Error> {
+    binary.get(start..end)
+        .ok_or_else(|| Error::parse("The transmission ends in the middle of a packet."))
 }
 
-fn parse_packets(mut binary: &[bool], amount: Option<usize>) -> (Vec<Packet>, usize) {
+fn parse_packets(mut binary: &[bool], amount: Option<usize>) -> Result<(Vec<Packet>, usize), Error> {
     let mut packets = Vec::<Packet>::new();
     let mut end_index = 0;
     while binary.len() > 4 {
@@ -51,59 +47,71 @@ fn parse_packets(mut binary: &[bool], amount: Option<usize>) -> (Vec<Packet>, us
             Some(max) => if packets.len() == max { break; },
             None => ()
         }
-        let version = bin_to_dec(&binary[0..3]);
-        let packet_type_id = bin_to_dec(&binary[3..6]);
+        let version = bin_to_dec(bits(binary, 0, 3)?);
+        let packet_type_id = bin_to_dec(bits(binary, 3, 6)?);
         let (payload, payload_size) = match packet_type_id {
-            4 => parse_literal_payload(&binary[6..]),
-            n => parse_operator_payload(&binary[6..], n)
+            4 => parse_literal_payload(&binary[6..])?,
+            n => parse_operator_payload(&binary[6..], n)?
         };
         end_index += 6+payload_size;
         binary = &binary[6+payload_size..];
         packets.push(Packet{version, payload});
     }
-    (packets, end_index)
+    match amount {
+        Some(max) if packets.len() < max =>
+            Err(Error::parse(format!("Expected {} sub-packets, found {}.", max, packets.len()))),
+        _ => Ok((packets, end_index))
+    }
 }
 
-fn parse_literal_payload(binary: &[bool]) -> (PacketPayload, usize) {
+fn parse_literal_payload(binary: &[bool]) -> Result<(PacketPayload, usize), Error> {
     let mut i = 0;
     let mut stop = false;
     let mut literal_bin = Vec::<bool>::new();
     while !stop {
-        stop |= !binary[i];
-        literal_bin.extend(&binary[i+1..i+5]);
+        let group = bits(binary, i, i+5)?;
+        stop |= !group[0];
+        literal_bin.extend(&group[1..]);
         i += 5;
     }
+    if literal_bin.len() > 64 {
+        return Err(Error::parse("A literal value doesn't fit in 64 bits."));
+    }
     let literal = bin_to_dec(&literal_bin);
-    (PacketPayload::Literal(literal), i)
+    Ok((PacketPayload::Literal(literal), i))
 }
 
-fn parse_operator_payload(binary: &[bool], type_id: u64) -> (PacketPayload, usize) {
-    let length_type_flag = binary[0];
+fn parse_operator_payload(binary: &[bool], type_id: u64) -> Result<(PacketPayload, usize), Error> {
+    let length_type_flag = bits(binary, 0, 1)?[0];
     if !length_type_flag {
         // next 15 bits represent total length in bits of sub-packets
-        let bit_len = bin_to_dec(&binary[1..16]) as usize;
-        let (packets, _) = parse_packets(&binary[16..16+bit_len], None);
-        (build_operator_payload(packets, type_id), 16+bit_len)
+        let bit_len = bin_to_dec(bits(binary, 1, 16)?) as usize;
+        let (packets, _) = parse_packets(bits(binary, 16, 16+bit_len)?, None)?;
+        Ok((build_operator_payload(packets, type_id)?, 16+bit_len))
     } else {
         // next 11 bits represent total amount of sub-packets
-        let packet_len = bin_to_dec(&binary[1..12]) as usize;
-        let (packets, payload_size) = parse_packets(&binary[12..], Some(packet_len));
-        (build_operator_payload(packets, type_id), 12+payload_size)
+        let packet_len = bin_to_dec(bits(binary, 1, 12)?) as usize;
+        let (packets, payload_size) = parse_packets(&binary[12..], Some(packet_len))?;
+        Ok((build_operator_payload(packets, type_id)?, 12+payload_size))
     }
 }
 
-fn build_operator_payload(packets: Vec<Packet>, type_id: u64) -> PacketPayload {
+fn build_operator_payload(packets: Vec<Packet>, type_id: u64) -> Result<PacketPayload, Error> {
+    if packets.is_empty() {
+        return Err(Error::parse("An operator packet must have sub-packets."));
+    }
+    if (5..=7).contains(&type_id) && packets.len() != 2 {
+        return Err(Error::parse("A comparison packet must have exactly two sub-packets."));
+    }
     match type_id {
-        0 => PacketPayload::Sum(packets),
-        1 => PacketPayload::Product(packets),
-        2 => PacketPayload::Minimum(packets),
-        3 => PacketPayload::Maximum(packets),
-        5 => PacketPayload::GreaterThan(packets),
-        6 => PacketPayload::LessThan(packets),
-        7 => PacketPayload::EqualTo(packets),
-        _ => {
-            panic!("Invalid operator packet type.");
-        }
+        0 => Ok(PacketPayload::Sum(packets)),
+        1 => Ok(PacketPayload::Product(packets)),
+        2 => Ok(PacketPayload::Minimum(packets)),
+        3 => Ok(PacketPayload::Maximum(packets)),
+        5 => Ok(PacketPayload::GreaterThan(packets)),
+        6 => Ok(PacketPayload::LessThan(packets)),
+        7 => Ok(PacketPayload::EqualTo(packets)),
+        _ => Err(Error::parse("Invalid operator packet type."))
     }
 }
 
@@ -166,7 +174,7 @@ fn eval_packet(packet: &Packet) -> u64 {
     }
 }
 
-struct Packet {
+pub struct Packet {
     version: u64,
     payload: PacketPayload
 }
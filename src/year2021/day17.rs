@@ -1,33 +1,41 @@
 use std::cmp::{min, max};
 
+use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::{input_file::read_all_to_string, data_structs::SignedCoord};
+use crate::{input_file::{read_all_to_string, PuzzleInput}, data_structs::SignedCoord};
+use crate::captures::{group, parse_captures};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-  let input = read_all_to_string(input_file_path);
-  let goal = parse_input(&input);
-  let peak = get_highest_trajectory(&goal);
-  println!("{}", peak);
+pub fn parse(input: &PuzzleInput) -> Result<Region, Error> {
+  parse_input(&read_all_to_string(input)?)
 }
 
-pub fn part2(input_file_path: &str) {
-  let input = read_all_to_string(input_file_path);
-  let goal = parse_input(&input);
-  let trajs = get_possible_trajectories(&goal);
+pub fn part1(goal: &Region) -> Result<Answer, Error> {
+  let peak = get_highest_trajectory(goal);
+  Ok(Answer::from(peak))
+}
+
+pub fn part2(goal: &Region) -> Result<Answer, Error> {
+  let trajs = get_possible_trajectories(goal);
   let amount = trajs.len();
   // for traj in trajs.into_iter().sorted() {
   //   println!("{:?}", traj);
   // }
-  println!("{}", amount);
+  Ok(Answer::from(amount))
+}
+
+lazy_static! {
+  static ref TARGET_RE: Regex = Regex::new(r"target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)").unwrap();
 }
 
-fn parse_input(input: &str) -> Region {
-  let regex = Regex::new(r"target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)").unwrap();
-  let values: Vec<isize> = regex.captures(input).unwrap().iter().skip(1).map(|s|
-    s.unwrap().as_str().parse().expect("Could not parse input coordinates.")
-  ).collect();
-  Region {
+fn parse_input(input: &str) -> Result<Region, Error> {
+  let values: Vec<isize> = parse_captures(&TARGET_RE, input, |c| (1..=4).map(|i| group(c, i)).collect())?;
+  if min(values[0], values[1]) <= 0 && max(values[0], values[1]) >= 0 {
+    return Err(Error::parse("The target area can't be directly above or below the probe."));
+  }
+  Ok(Region {
     top_left: SignedCoord {
       x: min(values[0], values[1]),
       y: max(values[2], values[3]),
@@ -36,10 +44,10 @@ fn parse_input(input: &str) -> Region {
       x: max(values[0], values[1]),
       y: min(values[2], values[3]),
     }
-  }
+  })
 }
 
-struct Region {
+pub struct Region {
   top_left: SignedCoord,
   bottom_right: SignedCoord
 }
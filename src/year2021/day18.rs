@@ -1,18 +1,27 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
-  let snail_nums = parse_lines(&lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Vec<SnailfishToken>>, Error> {
+  let snail_nums = parse_each_line(input, |line| {
+    check_snailfish_number(line)?;
+    Ok(parse_snailfish_number(line))
+  })?;
+  if snail_nums.is_empty() {
+    return Err(Error::parse("There are no snailfish numbers to add."));
+  }
+  Ok(snail_nums)
+}
+
+pub fn part1(snail_nums: &[Vec<SnailfishToken>]) -> Result<Answer, Error> {
   let mut total = snail_nums[0].clone();
   for i in 1..snail_nums.len() {
     total = add_snailfish_numbers(&total, &snail_nums[i]);
   }
-  println!("{}", magnitude(&total));
+  Ok(Answer::from(magnitude(&total)))
 }
 
-pub fn part2(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
-  let snail_nums = parse_lines(&lines);
+pub fn part2(snail_nums: &[Vec<SnailfishToken>]) -> Result<Answer, Error> {
   let mut best_magnitude = 0;
   for i in 0..snail_nums.len() {
     for j in 0..snail_nums.len() {
@@ -27,16 +36,49 @@ pub fn part2(input_file_path: &str) {
       }
     }
   }
-  println!("{}", best_magnitude);
+  Ok(Answer::from(best_magnitude))
+}
+
+/// Make sure the line is a pair of two elements, each a number or a
+/// pair in turn, nested no more than four deep, so the tokens that
+/// parse_snailfish_number gives always reduce.
+fn check_snailfish_number(numstr: &str) -> Result<(), Error> {
+  let chars: Vec<char> = numstr.chars().collect();
+  match check_pair(&chars, 0, 1)? {
+    end if end == chars.len() => Ok(()),
+    end => Err(Error::parse(format!("Unexpected {} after the number.", chars[end])))
+  }
+}
+
+/// Check the pair starting at i, giving the index just after it.
+fn check_pair(chars: &[char], i: usize, depth: u32) -> Result<usize, Error> {
+  if depth > 4 {
+    return Err(Error::parse("Pairs are nested more than four deep."));
+  }
+  let i = expect_char(chars, i, '[')?;
+  let i = check_element(chars, i, depth)?;
+  let i = expect_char(chars, i, ',')?;
+  let i = check_element(chars, i, depth)?;
+  expect_char(chars, i, ']')
+}
+
+fn check_element(chars: &[char], i: usize, depth: u32) -> Result<usize, Error> {
+  if chars.get(i) == Some(&'[') {
+    return check_pair(chars, i, depth + 1);
+  }
+  let digits = chars[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+  if digits == 0 || digits > 2 {
+    return Err(Error::parse("Expected a pair or a regular number below 100."));
+  }
+  Ok(i + digits)
 }
 
-fn parse_lines(lines: &Vec<String>) -> Vec<Vec<SnailfishToken>> {
-  let mut snail_nums = Vec::<Vec<SnailfishToken>>::new();
-  for line in lines {
-    let tokens = parse_snailfish_number(line);
-    snail_nums.push(tokens);
+fn expect_char(chars: &[char], i: usize, expected: char) -> Result<usize, Error> {
+  match chars.get(i) {
+    Some(c) if *c == expected => Ok(i + 1),
+    Some(c) => Err(Error::parse(format!("Expected {}, found {}.", expected, c))),
+    None => Err(Error::parse(format!("Expected {}, found the end of the line.", expected)))
   }
-  snail_nums
 }
 
 fn parse_snailfish_number(numstr: &str) -> Vec<SnailfishToken> {
@@ -48,7 +90,7 @@ fn parse_snailfish_number(numstr: &str) -> Vec<SnailfishToken> {
     match c {
       '[' | ']' | ',' => {
         if current_number.len() > 0 {
-          let value = current_number.parse().unwrap();
+          let value = current_number.parse().expect("Numbers are checked when parsing.");
           tokens.push(SnailfishToken{value, nest_level});
         }
         current_number = String::new();
@@ -67,7 +109,7 @@ fn parse_snailfish_number(numstr: &str) -> Vec<SnailfishToken> {
 }
 
 #[derive(Clone)]
-struct SnailfishToken {
+pub struct SnailfishToken {
   value: u32,
   nest_level: i32
 }
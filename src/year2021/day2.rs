@@ -1,8 +1,12 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, parse_value, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instructions = parse_lines(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Instruction>, Error> {
+    parse_each_line(input, parse_instruction)
+}
+
+pub fn part1(instructions: &[Instruction]) -> Result<Answer, Error> {
     let mut sub_coords = (0, 0);
     for inst in instructions {
         match inst {
@@ -11,12 +15,10 @@ pub fn part1(input_file_path: &str) {
             Instruction::Up(v) => sub_coords.1 -= v,
         }
     }
-    println!("{}", sub_coords.0 * sub_coords.1);
+    Ok(Answer::from(sub_coords.0 * sub_coords.1))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instructions = parse_lines(lines);
+pub fn part2(instructions: &[Instruction]) -> Result<Answer, Error> {
     let mut sub_coords = (0, 0);
     let mut aim = 0;
     for inst in instructions {
@@ -29,29 +31,24 @@ pub fn part2(input_file_path: &str) {
             Instruction::Up(v) => aim -= v,
         }
     }
-    println!("{}", sub_coords.0 * sub_coords.1);
+    Ok(Answer::from(sub_coords.0 * sub_coords.1))
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<Instruction> {
-    let mut instructions = Vec::<Instruction>::new();
-    for line in lines {
-        let parts = line.split(' ').collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            panic!("Invalid syntax for instruction.");
-        }
-        let amount = parts[1].parse::<u32>().expect("Could not parse value in instruction.");
-        let instruction = match parts[0] {
-            "forward" => Instruction::Forward(amount),
-            "down" => Instruction::Down(amount),
-            "up" => Instruction::Up(amount),
-            _ => panic!("Unhandled instruction type")
-        };
-        instructions.push(instruction);
+fn parse_instruction(line: &str) -> Result<Instruction, Error> {
+    let parts = line.split(' ').collect::<Vec<&str>>();
+    if parts.len() != 2 {
+        return Err(Error::parse("Invalid syntax for instruction."));
+    }
+    let amount = parse_value::<u32>(parts[1])?;
+    match parts[0] {
+        "forward" => Ok(Instruction::Forward(amount)),
+        "down" => Ok(Instruction::Down(amount)),
+        "up" => Ok(Instruction::Up(amount)),
+        other => Err(Error::parse(format!("Unhandled instruction type {}.", other)))
     }
-    instructions
 }
 
-enum Instruction {
+pub enum Instruction {
     Forward(u32),
     Up(u32),
     Down(u32)
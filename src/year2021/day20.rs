@@ -1,50 +1,70 @@
 use std::{collections::HashSet, ops::Add, iter::FromIterator};
 
 use crate::{
-  input_file::read_lines,
+  input_file::{parse_two_sections, PuzzleInput},
   binary::bin_to_dec,
   data_structs::SignedCoord
 };
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
-  let (enhancer, mut lit_pixels) = parse_lines(&lines);
+const DEBUGGING: bool = false;
+
+pub fn parse(input: &PuzzleInput) -> Result<Image, Error> {
+  let (enhancer, (lit_pixels, width, height)) = parse_two_sections(
+    input,
+    |lines| Ok(lines.concat().chars().map(|c| c == '#').collect()),
+    |lines| Ok((parse_lit_pixels(lines), lines[0].len(), lines.len()))
+  )?;
+  Ok(Image { enhancer, lit_pixels, width, height })
+}
+
+pub fn part1(image: &Image) -> Result<Answer, Error> {
+  let (enhancer, mut lit_pixels) = (&image.enhancer, image.lit_pixels.clone());
   let top_left = SignedCoord{x: -10, y: -10};
-  let bottom_right = SignedCoord{x: (lines[2].len()+9) as isize, y: (lines.len()+9) as isize};
+  let bottom_right = SignedCoord{x: (image.width+9) as isize, y: (image.height+11) as isize};
   for _ in 0..2 {
-    lit_pixels = enhance(lit_pixels, &enhancer, &top_left, &bottom_right);
-    print_lit_pixels(&lit_pixels, &top_left, &bottom_right);
+    lit_pixels = enhance(lit_pixels, enhancer, &top_left, &bottom_right);
+    if DEBUGGING {
+      print_lit_pixels(&lit_pixels, &top_left, &bottom_right);
+    }
   }
-  println!("{}", lit_pixels.iter().filter(|p| p.x != top_left.x && p.x != bottom_right.x).count());
+  Ok(Answer::from(lit_pixels.iter().filter(|p| p.x != top_left.x && p.x != bottom_right.x).count()))
 }
 
-pub fn part2(input_file_path: &str) {
-  let lines = read_lines(input_file_path);
-  let (enhancer, mut lit_pixels) = parse_lines(&lines);
+pub fn part2(image: &Image) -> Result<Answer, Error> {
+  let (enhancer, mut lit_pixels) = (&image.enhancer, image.lit_pixels.clone());
   let top_left = SignedCoord{x: -53, y: -53};
-  let bottom_right = SignedCoord{x: (lines[2].len()+52) as isize, y: (lines.len()+52) as isize};
+  let bottom_right = SignedCoord{x: (image.width+52) as isize, y: (image.height+54) as isize};
   for i in 0..50 {
-    lit_pixels = enhance(lit_pixels, &enhancer, &top_left, &bottom_right);
+    lit_pixels = enhance(lit_pixels, enhancer, &top_left, &bottom_right);
     if i % 2 == 1 {
       lit_pixels = HashSet::from_iter(lit_pixels.into_iter().filter(|p| p.x != top_left.x && p.x != bottom_right.x));
     }
     // print_lit_pixels(&lit_pixels, &top_left, &bottom_right);
   }
-  println!("{}", lit_pixels.iter().filter(|p| p.x != top_left.x && p.x != bottom_right.x).count());
+  Ok(Answer::from(lit_pixels.iter().filter(|p| p.x != top_left.x && p.x != bottom_right.x).count()))
+}
+
+/// The image enhancement algorithm, and the pixels that
+/// are lit in the input image along with its size.
+pub struct Image {
+  enhancer: Vec<bool>,
+  lit_pixels: HashSet<SignedCoord>,
+  width: usize,
+  height: usize
 }
 
-fn parse_lines(lines: &Vec<String>) -> (Vec<bool>, HashSet<SignedCoord>) {
-  let enhancer = lines[0].chars().map(|c| c == '#').collect();
+fn parse_lit_pixels(lines: &[String]) -> HashSet<SignedCoord> {
   let mut lit_pixels = HashSet::new();
-  for i in 2..lines.len() {
-    let y = i as isize - 2;
-    for (x, c) in lines[i].chars().enumerate() {
+  for (y, line) in lines.iter().enumerate() {
+    for (x, c) in line.chars().enumerate() {
       if c == '#' {
-        lit_pixels.insert(SignedCoord{x: x as isize, y});
+        lit_pixels.insert(SignedCoord{x: x as isize, y: y as isize});
       }
     }
   }
-  (enhancer, lit_pixels)
+  lit_pixels
 }
 
 fn enhance(
@@ -2,29 +2,35 @@ use std::{cmp::{min, max}, collections::HashMap};
 use lazy_static::lazy_static;
 
 use crate::{
-  input_file::read_lines
+  input_file::{parse_each_line, parse_value, PuzzleInput}
 };
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (p1_pos, p2_pos) = parse_lines(&lines);
+pub fn parse(input: &PuzzleInput) -> Result<(u8, u8), Error> {
+    match parse_each_line(input, parse_start)?[..] {
+        [p1_pos, p2_pos] => Ok((p1_pos, p2_pos)),
+        _ => Err(Error::parse("Expected the starting positions of two players."))
+    }
+}
+
+pub fn part1(&(p1_pos, p2_pos): &(u8, u8)) -> Result<Answer, Error> {
     let die = (1..101).cycle();
     let (loser_score, rolls) = play_determinist_dice(p1_pos, p2_pos, die);
-    println!("{}", loser_score * rolls);
+    Ok(Answer::from(loser_score * rolls))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (p1_pos, p2_pos) = parse_lines(&lines);
+pub fn part2(&(p1_pos, p2_pos): &(u8, u8)) -> Result<Answer, Error> {
     let (p1_wins, p2_wins) = take_dirac_turn(p1_pos, p2_pos, 0, 0, true);
-    println!("{}", max(p1_wins, p2_wins));
+    Ok(Answer::from(max(p1_wins, p2_wins)))
 }
 
-fn parse_lines(lines: &Vec<String>) -> (u8, u8) {
-    (
-        lines[0].split(' ').last().unwrap().parse().expect("Could not parse starting pos"),
-        lines[1].split(' ').last().unwrap().parse().expect("Could not parse starting pos"),
-    )
+fn parse_start(line: &str) -> Result<u8, Error> {
+    let pos = parse_value(line.split(' ').last().unwrap_or_default())?;
+    if !(1..=10).contains(&pos) {
+        return Err(Error::parse("The starting position must be from 1 to 10."));
+    }
+    Ok(pos)
 }
 
 fn play_determinist_dice<I>(p1_start_pos: u8, p2_start_pos: u8, mut die: I) -> (u32, u32)
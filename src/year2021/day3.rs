@@ -1,11 +1,28 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    let lines = parse_each_line(input, |line| {
+        if line.is_empty() || line.len() > 31 {
+            return Err(Error::parse("Expected a binary number of 1 to 31 bits."));
+        }
+        if !line.chars().all(|c| c == '0' || c == '1') {
+            return Err(Error::parse("String is not a binary number."));
+        }
+        Ok(line.to_string())
+    })?;
     if lines.is_empty() {
-        panic!("No data in input file.");
+        return Err(Error::parse("No data in input file."));
     }
-    let common_bits = most_common_bits(&lines);
+    if let Some(i) = lines.iter().position(|line| line.len() != lines[0].len()) {
+        return Err(Error::parse("Lines have unequal amounts of bits.").at_line(i + 1, &lines[i]));
+    }
+    Ok(lines)
+}
+
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
+    let common_bits = most_common_bits(lines);
     let mut gamma = 0;
     let mut epsilon = 0;
     for i in 0..common_bits.len() {
@@ -18,21 +35,17 @@ pub fn part1(input_file_path: &str) {
             epsilon += 2i32.pow(exp);
         }
     }
-    println!("{}", gamma * epsilon);
+    Ok(Answer::from(gamma * epsilon))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    if lines.is_empty() {
-        panic!("No data in input file.");
-    }
-    let oxygen_rating = calculate_rating(&lines, true);
-    let co2_rating = calculate_rating(&lines, false);
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
+    let oxygen_rating = calculate_rating(lines, true);
+    let co2_rating = calculate_rating(lines, false);
     let safety_rating = oxygen_rating * co2_rating;
-    println!("{}", safety_rating);
+    Ok(Answer::from(safety_rating))
 }
 
-fn most_common_bits(numbers: &Vec<String>) -> Vec<i32> {
+fn most_common_bits(numbers: &[String]) -> Vec<i32> {
     let mut counts = vec![0; numbers[0].len()];
     for num in numbers {
         let bits: Vec<char> = num.chars().collect();
@@ -51,8 +64,8 @@ fn most_common_bits(numbers: &Vec<String>) -> Vec<i32> {
 
 }
 
-fn calculate_rating(numbers: &Vec<String>, use_most_common: bool) -> u32 {
-    let mut candidates = numbers.clone();
+fn calculate_rating(numbers: &[String], use_most_common: bool) -> u32 {
+    let mut candidates = numbers.to_vec();
     for i in 0..numbers[0].len() {
         // if one left, we're done
         if candidates.len() == 1 {
@@ -1,47 +1,48 @@
-use itertools::Itertools;
+use crate::{data_structs::Grid, input_file::{parse_value, read_sections, split_parse, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-use crate::{data_structs::Grid, input_file::read_lines};
+pub fn parse(input: &PuzzleInput) -> Result<(Vec<u32>, Vec<Grid<BingoEntry>>), Error> {
+    let sections = read_sections(input)?;
+    let (called, boards) = match &sections[..] {
+        [called, boards @ ..] if called.len() == 1 => (&called[0], boards),
+        _ => return Err(Error::parse("Expected the called numbers on the first line."))
+    };
+    let called_nums = split_parse(called, ",")?;
+    let grids = boards.iter()
+        .enumerate()
+        .map(|(i, board)| parse_bingo_grid(board)
+            .map_err(|e| Error::parse(format!("Board {}: {}", i + 1, e.message))))
+        .collect::<Result<_, _>>()?;
+    Ok((called_nums, grids))
+}
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (nums, grids) = parse_lines(lines);
-    let (last_num, winner) = find_bingo_winner(nums, grids, false).unwrap();
+pub fn part1((nums, grids): &(Vec<u32>, Vec<Grid<BingoEntry>>)) -> Result<Answer, Error> {
+    let (last_num, winner) = find_bingo_winner(nums.clone(), grids.clone(), false)
+        .ok_or_else(|| Error::solve("No board ever wins."))?;
     let score = calculate_score(&winner, last_num);
-    println!("{}", score);
+    Ok(Answer::from(score))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (nums, grids) = parse_lines(lines);
-    let (last_num, winner) = find_bingo_winner(nums, grids, true).unwrap();
+pub fn part2((nums, grids): &(Vec<u32>, Vec<Grid<BingoEntry>>)) -> Result<Answer, Error> {
+    let (last_num, winner) = find_bingo_winner(nums.clone(), grids.clone(), true)
+        .ok_or_else(|| Error::solve("No board ever wins."))?;
     let score = calculate_score(&winner, last_num);
-    println!("{}", score);
+    Ok(Answer::from(score))
 }
 
-fn parse_lines(lines: Vec<String>) -> (Vec<u32>, Vec<Grid<BingoEntry>>) {
-    let called_nums = 
-        lines[0]
-        .split(',')
-        .map(|nstr| nstr.parse::<u32>().expect("Called number doesn't parse."))
-        .collect_vec();
-    let mut grids = Vec::<Grid<BingoEntry>>::new();
-    for i in (1..lines.len()).step_by(6) {
-        grids.push(parse_bingo_grid(&lines[i+1..i+6]))
+fn parse_bingo_grid(lines: &[String]) -> Result<Grid<BingoEntry>, Error> {
+    // Parse the numbers in each line and create
+    // a grid with all the numbers.
+    let mut elements = Vec::<BingoEntry>::new();
+    for line in lines {
+        let row = line.split_whitespace().map(parse_value::<u32>).collect::<Result<Vec<_>, _>>()?;
+        if row.len() != lines.len() {
+            return Err(Error::parse("A board must be a square of numbers."));
+        }
+        elements.extend(row.into_iter().map(|num| BingoEntry { num, marked: false }));
     }
-    (called_nums, grids)
-}
-
-fn parse_bingo_grid(lines: &[String]) -> Grid<BingoEntry> {
-    // Chain iterators over the parsed numbers in each line
-    // and create a grid with all the numbers.
-    let elements: Vec<BingoEntry> = lines.iter().flat_map(
-        |line|
-        line.trim()
-            .split_whitespace()
-            .map(|nstr| nstr.parse::<u32>().expect("Called number doesn't parse."))
-            .map(|num| BingoEntry { num, marked: false })
-    ).collect_vec();
-    Grid::<BingoEntry>::with_elements(lines.len(), lines.len(), elements)
+    Ok(Grid::<BingoEntry>::with_elements(lines.len(), lines.len(), elements))
 }
 
 fn find_bingo_winner(
@@ -74,36 +75,17 @@ fn find_bingo_winner(
 }
 
 fn mark_number(grid: &mut Grid<BingoEntry>, num: u32) {
-    for x in 0..grid.xsize {
-        for y in 0..grid.ysize {
-            let entry = grid.get_mut(x, y);
-            if entry.num == num {
-                entry.marked = true;
-            }
+    for entry in grid.iter_mut() {
+        if entry.num == num {
+            entry.marked = true;
         }
     }
 }
 
 fn has_won(grid: &Grid<BingoEntry>) -> bool {
-    // check columns
-    for x in 0..grid.xsize {
-        let won = (0..grid.ysize)
-            .map(|y| grid.get(x, y))
-            .all(|e| e.marked);
-        if won {
-            return true;
-        }
-    }
-    // check rows
-    for y in 0..grid.ysize {
-        let won = (0..grid.xsize)
-            .map(|x| grid.get(x, y))
-            .all(|e| e.marked);
-        if won {
-            return true;
-        }
-    }
-    false
+    // check columns, then rows
+    grid.columns().any(|mut column| column.all(|e| e.marked)) ||
+        grid.rows().any(|row| row.iter().all(|e| e.marked))
 }
 
 fn calculate_score(grid: &Grid<BingoEntry>, called_num: u32) -> u32 {
@@ -115,7 +97,7 @@ fn calculate_score(grid: &Grid<BingoEntry>, called_num: u32) -> u32 {
 }
 
 #[derive(Clone)]
-struct BingoEntry {
+pub struct BingoEntry {
     num: u32,
     marked: bool
 }
\ No newline at end of file
@@ -1,38 +1,41 @@
 use std::{cmp::{max, min}, collections::HashSet};
 
-use crate::{data_structs::Coord, input_file::read_lines};
+use crate::{data_structs::Coord, input_file::{parse_each_line, split_parse, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let vents = parse_lines(lines);
-    let crossings = find_crossings(vents, false);
-    println!("{}", crossings.len());
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Vent>, Error> {
+    parse_each_line(input, parse_vent)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let vents = parse_lines(lines);
-    let crossings = find_crossings(vents, true);
-    println!("{}", crossings.len());
+pub fn part1(vents: &[Vent]) -> Result<Answer, Error> {
+    let crossings = find_crossings(vents.to_vec(), false);
+    Ok(Answer::from(crossings.len()))
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<Vent> {
-    let mut vents = Vec::<Vent>::new();
-    for line in lines {
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        let start_parts: Vec<&str> = parts[0].split(',').collect();
-        let end_parts: Vec<&str> = parts[1].split(',').collect();
-        let start = Coord {
-            x: start_parts[0].parse().expect("Could not parse x1 in line."),
-            y: start_parts[1].parse().expect("Could not parse y1 in line."),
-        };
-        let end = Coord {
-            x: end_parts[0].parse().expect("Could not parse x2 in line."),
-            y: end_parts[1].parse().expect("Could not parse y2 in line."),
-        };
-        vents.push(Vent { start, end });
+pub fn part2(vents: &[Vent]) -> Result<Answer, Error> {
+    let crossings = find_crossings(vents.to_vec(), true);
+    Ok(Answer::from(crossings.len()))
+}
+
+fn parse_vent(line: &str) -> Result<Vent, Error> {
+    let parts: Vec<&str> = line.split(" -> ").collect();
+    let (start, end) = match &parts[..] {
+        [start, end] => (parse_coord(start)?, parse_coord(end)?),
+        _ => return Err(Error::parse("Expected a vent like x1,y1 -> x2,y2."))
+    };
+    let (xdist, ydist) = (max(start.x, end.x) - min(start.x, end.x), max(start.y, end.y) - min(start.y, end.y));
+    if xdist != 0 && ydist != 0 && xdist != ydist {
+        return Err(Error::parse("Vents must be horizontal, vertical or at 45 degrees."));
+    }
+    Ok(Vent { start, end })
+}
+
+fn parse_coord(text: &str) -> Result<Coord, Error> {
+    match split_parse(text, ",")?[..] {
+        [x, y] => Ok(Coord { x, y }),
+        _ => Err(Error::parse(format!("Expected a coordinate like x,y, not {}.", text)))
     }
-    vents
 }
 
 fn find_crossings(vents: Vec<Vent>, allow_diagonal: bool) -> HashSet<Coord> {
@@ -82,7 +85,8 @@ fn find_crossings(vents: Vec<Vent>, allow_diagonal: bool) -> HashSet<Coord> {
     crossings
 }
 
-struct Vent {
+#[derive(Clone)]
+pub struct Vent {
     start: Coord,
     end: Coord
 }
\ No newline at end of file
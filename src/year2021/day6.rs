@@ -1,27 +1,19 @@
-use crate::input_file::read_all_to_string;
+use crate::input_file::{read_all_to_string, split_parse, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let fish = parse_input(input);
-    let fish = simulate_fish(fish, 80);
-    println!("{}", fish);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<u8>, Error> {
+    split_parse(&read_all_to_string(input)?, ",")
 }
 
-pub fn part2(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let fish = parse_input(input);
-    let fish = simulate_fish(fish, 256);
-    println!("{}", fish);
+pub fn part1(fish: &[u8]) -> Result<Answer, Error> {
+    let fish = simulate_fish(fish.to_vec(), 80);
+    Ok(Answer::from(fish))
 }
 
-fn parse_input(input: String) -> Vec<u8> {
-    input
-        .split(',')
-        .map(
-            |s|
-            s.parse().expect("Failed to parse input number")
-        )
-        .collect()
+pub fn part2(fish: &[u8]) -> Result<Answer, Error> {
+    let fish = simulate_fish(fish.to_vec(), 256);
+    Ok(Answer::from(fish))
 }
 
 // fn simulate_fish(fish: Vec<u8>, days: usize) -> Vec<u8> {
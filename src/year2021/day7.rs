@@ -1,31 +1,23 @@
-use crate::input_file::read_all_to_string;
+use crate::input_file::{read_all_to_string, split_parse, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 use lazy_static::lazy_static;
 
-pub fn part1(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let crabs = parse_input(input);
-    let fuel = best_crab_fuel(&crabs, false);
-    println!("{}", fuel);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<u32>, Error> {
+    split_parse(&read_all_to_string(input)?, ",")
 }
 
-pub fn part2(input_file_path: &str) {
-    let input = read_all_to_string(input_file_path);
-    let crabs = parse_input(input);
-    let fuel = best_crab_fuel(&crabs, true);
-    println!("{}", fuel);
+pub fn part1(crabs: &[u32]) -> Result<Answer, Error> {
+    let fuel = best_crab_fuel(crabs, false);
+    Ok(Answer::from(fuel))
 }
 
-fn parse_input(input: String) -> Vec<u32> {
-    input
-        .split(',')
-        .map(
-            |s|
-            s.parse().expect("Failed to parse input number")
-        )
-        .collect()
+pub fn part2(crabs: &[u32]) -> Result<Answer, Error> {
+    let fuel = best_crab_fuel(crabs, true);
+    Ok(Answer::from(fuel))
 }
 
-fn best_crab_fuel(crabs: &Vec<u32>, triangular: bool) -> u32 {
+fn best_crab_fuel(crabs: &[u32], triangular: bool) -> u32 {
     let max_pos = crabs.iter().max().unwrap();
     let mut best_fuel = u32::MAX;
     for pos in 0..*max_pos {
@@ -51,7 +43,7 @@ lazy_static! {
     static ref TRIANGLE_NUMS: [usize; 2000] = triangle_nums();
 }
 
-fn calculate_fuel_used(crabs: &Vec<u32>, pos: u32, triangular: bool) -> u32 {
+fn calculate_fuel_used(crabs: &[u32], pos: u32, triangular: bool) -> u32 {
     crabs.iter()
         .map(
             |crab| {
@@ -1,24 +1,29 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
+
+/// The ten unique signal patterns and the four output digits of a display.
+type Display = (Vec<String>, Vec<String>);
 
 use self::wire_mapping::WireMappingTable;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let displays = parse_lines(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Display>, Error> {
+    parse_each_line(input, parse_display)
+}
+
+pub fn part1(displays: &[Display]) -> Result<Answer, Error> {
     let mut count = 0;
     for display in displays {
-        for output_digit in display.1 {
+        for output_digit in &display.1 {
             if [2, 3, 4, 7].contains(&output_digit.len()) {
                 count += 1;
             }
         }
     }
-    println!("{}", count);
+    Ok(Answer::from(count))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let displays = parse_lines(lines);
+pub fn part2(displays: &[Display]) -> Result<Answer, Error> {
     let mut total = 0;
     for display in displays {
         let mut mapping = WireMappingTable::new();
@@ -36,24 +41,35 @@ pub fn part2(input_file_path: &str) {
         // Calculate output number
         let mut output = 0;
         for (i, digit) in display.1.iter().enumerate() {
-            let num = mapping.wires_to_digit(digit);
+            let num = mapping.wires_to_digit(digit)?;
             let exp = display.1.len() - i - 1;
             output += num * (10usize.pow(exp as u32));
         }
         // Add to total
         total += output;
     }
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-fn parse_lines(lines: Vec<String>) -> Vec<(Vec<String>, Vec<String>)> {
-    lines.iter().map(|line| {
-        let parts = line
-            .split('|')
-            .map(|p| p.trim().split_whitespace().map(|s| s.to_string()).collect())
-            .collect::<Vec<Vec<String>>>();
-        (parts[0].clone(), parts[1].clone())
-    }).collect()
+fn parse_display(line: &str) -> Result<Display, Error> {
+    let parts = line
+        .split('|')
+        .map(|p| p.trim().split_whitespace().map(|s| s.to_string()).collect())
+        .collect::<Vec<Vec<String>>>();
+    let (patterns, outputs) = match &parts[..] {
+        [patterns, outputs] if patterns.len() == 10 && outputs.len() == 4 => (patterns, outputs),
+        _ => return Err(Error::parse("Expected ten signal patterns, then | and four output digits."))
+    };
+    for wires in patterns.iter().chain(outputs) {
+        let mut sorted: Vec<char> = wires.chars().collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let known_wires = wires.chars().all(|c| ('a'..='g').contains(&c));
+        if !known_wires || sorted.len() != wires.len() || !(2..=7).contains(&wires.len()) {
+            return Err(Error::parse(format!("{} is not a set of 2 to 7 wires from a to g.", wires)));
+        }
+    }
+    Ok((patterns.clone(), outputs.clone()))
 }
 
 mod wire_mapping {
@@ -61,6 +77,8 @@ mod wire_mapping {
     use std::{collections::{HashMap, HashSet}, iter::FromIterator};
     use lazy_static::lazy_static;
 
+    use crate::error::Error;
+
     lazy_static! {
         static ref WIRES: Vec<char> = vec!['a', 'b', 'c', 'd', 'e', 'f', 'g'];
         static ref DIGIT_SEGMENTS: [Vec<char>; 10] = [
@@ -101,12 +119,12 @@ mod wire_mapping {
             }
         }
 
-        pub fn wires_to_digit(&self, seven_segment: &str) -> usize {
+        pub fn wires_to_digit(&self, seven_segment: &str) -> Result<usize, Error> {
             let mut mapped_segments = Vec::new();
             for c in seven_segment.chars() {
-                let mapped = self.table.get(&c).unwrap();
+                let mapped = self.table.get(&c).expect("Wires are checked when parsing.");
                 if mapped.len() != 1 {
-                    panic!("More than one possibility remaining for wire.");
+                    return Err(Error::solve("More than one possibility remaining for wire."));
                 }
                 let mapped = mapped.iter().next().unwrap();
                 mapped_segments.push(*mapped);
@@ -114,10 +132,10 @@ mod wire_mapping {
             mapped_segments.sort();
             for (digit, segments) in DIGIT_SEGMENTS.iter().enumerate() {
                 if mapped_segments == *segments {
-                    return digit;
+                    return Ok(digit);
                 }
             }
-            panic!("No digit found that matches the given segments.");
+            Err(Error::solve("No digit found that matches the given segments."))
         }
 
         pub fn update(&mut self, digit: &str) -> bool {
@@ -1,29 +1,26 @@
 use std::collections::HashSet;
 
-use crate::{input_file::read_lines, data_structs::{Grid, Coord}};
+use crate::{input_file::{read_lines, PuzzleInput}, data_structs::{Grid, Coord}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_lines(lines);
-    let low_points = low_points(&grid);
-    let risk: u32 = low_points.iter().map(|p| *grid.get(p.x, p.y) as u32 + 1).sum();
-    println!("{}", risk);
+pub fn parse(input: &PuzzleInput) -> Result<Grid<u8>, Error> {
+    Grid::from_digits(&read_lines(input)?)
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_lines(lines);
-    let basins = get_basins(&grid);
-    let basin_mult = basins[0].len() * basins[1].len() * basins[2].len();
-    println!("{}", basin_mult);
+pub fn part1(grid: &Grid<u8>) -> Result<Answer, Error> {
+    let low_points = low_points(grid);
+    let risk: u32 = low_points.iter().map(|p| *grid.get(p.x, p.y) as u32 + 1).sum();
+    Ok(Answer::from(risk))
 }
 
-fn parse_lines(lines: Vec<String>) -> Grid<u8> {
-    let elements = lines.concat().chars().map(
-        |c|
-        c.to_digit(10).unwrap_or_else(|| panic!("Unable to parse character to int: {}", c)) as u8
-    ).collect();
-    Grid::<u8>::with_elements(lines[0].len(), lines.len(), elements)
+pub fn part2(grid: &Grid<u8>) -> Result<Answer, Error> {
+    let basins = get_basins(grid);
+    if basins.len() < 3 {
+        return Err(Error::solve(format!("Found {} basins, but need at least 3.", basins.len())));
+    }
+    let basin_mult = basins[0].len() * basins[1].len() * basins[2].len();
+    Ok(Answer::from(basin_mult))
 }
 
 fn low_points(grid: &Grid<u8>) -> Vec<Coord> {
@@ -31,16 +28,8 @@ fn low_points(grid: &Grid<u8>) -> Vec<Coord> {
     for x in 0..grid.xsize {
         for y in 0..grid.ysize {
             let current = grid.get(x, y);
-            if
-                // check left is lower
-                (x == 0 || grid.get(x-1, y) > current) &&
-                // check up is lower
-                (y == 0 || grid.get(x, y-1) > current) &&
-                // check right is lower
-                (x == grid.xsize - 1 || grid.get(x+1, y) > current) &&
-                // check down is lower
-                (y == grid.ysize - 1 || grid.get(x, y+1) > current)
-            {
+            // check every neighbour is higher
+            if grid.neighbours4(x, y).all(|(_, height)| height > current) {
                 low_points.push(Coord { x, y });
             }
         }
@@ -72,33 +61,11 @@ fn get_points_in_basin(grid: &Grid<u8>, coord: &Coord) -> HashSet<Coord> {
             continue;
         }
         all_basin_coords.insert(current_coord.to_owned());
-        // check left
-        if
-            current_coord.x > 0 &&
-            grid.get(current_coord.x - 1, current_coord.y) > current_height
-        {
-            unresolved_coords.insert(Coord{x: current_coord.x - 1, y: current_coord.y});
-        }
-        // check upwards
-        if
-            current_coord.y > 0 &&
-            grid.get(current_coord.x, current_coord.y - 1) > current_height
-        {
-            unresolved_coords.insert(Coord{x: current_coord.x, y: current_coord.y - 1});
-        }
-        // check right
-        if
-            current_coord.x < grid.xsize - 1 &&
-            grid.get(current_coord.x + 1, current_coord.y) > current_height
-        {
-            unresolved_coords.insert(Coord{x: current_coord.x + 1, y: current_coord.y});
-        }
-        // check upwards
-        if
-            current_coord.y < grid.ysize - 1 &&
-            grid.get(current_coord.x, current_coord.y + 1) > current_height
-        {
-            unresolved_coords.insert(Coord{x: current_coord.x, y: current_coord.y + 1});
+        // flow out to any higher neighbours
+        for (neighbour, height) in grid.neighbours4(current_coord.x, current_coord.y) {
+            if height > current_height {
+                unresolved_coords.insert(neighbour);
+            }
         }
     }
     all_basin_coords
@@ -1,51 +1,39 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, parse_value, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
 use std::{collections::HashMap, iter::zip};
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (left_list, right_list) = parse_input(lines);
-    let total = total_distance(left_list, right_list);
-    println!("{}", total)
+pub fn parse(input: &PuzzleInput) -> Result<(Vec<i32>, Vec<i32>), Error> {
+    // unzip converts the pairs into two containers, one containing
+    // all the left elements, and one containing all of the right elements
+    Ok(parse_each_line(input, parse_pair)?.into_iter().unzip())
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (left_list, right_list) = parse_input(lines);
-    let histogram = generate_histogram(&right_list);
+pub fn part1((left_list, right_list): &(Vec<i32>, Vec<i32>)) -> Result<Answer, Error> {
+    let total = total_distance(left_list.clone(), right_list.clone());
+    Ok(Answer::from(total))
+}
+
+pub fn part2((left_list, right_list): &(Vec<i32>, Vec<i32>)) -> Result<Answer, Error> {
+    let histogram = generate_histogram(right_list);
     let total_similarity: i32 =
-        left_list.into_iter()
+        left_list.iter()
             .map(|x| {
-                match histogram.get(&x) {
+                match histogram.get(x) {
                     Some(n) => x * (*n as i32),
                     None => 0,
                 }
             })
             .sum();
-    println!("{}", total_similarity);
+    Ok(Answer::from(total_similarity))
 }
 
-fn parse_input(lines: Vec<String>) -> (Vec<i32>, Vec<i32>) {
-    lines.into_iter()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            // construct a pair from the first two elements of the split.
-            // will panic if there's any less than two.
-            (
-                parts.next()
-                    .unwrap()
-                    .parse::<i32>()
-                    .unwrap(),
-                parts.next()
-                    .unwrap()
-                    .parse::<i32>()
-                    .unwrap()
-            )
-        })
-        // unzip converts an iterator of pairs into two containers,
-        // one containing all the left elements, and one containing
-        // all of the right elements
-        .unzip()
+fn parse_pair(line: &str) -> Result<(i32, i32), Error> {
+    match line.split_whitespace().map(parse_value).collect::<Result<Vec<i32>, _>>()?[..] {
+        [left, right] => Ok((left, right)),
+        _ => Err(Error::parse("Expected a number from each list."))
+    }
 }
 
 fn total_distance(mut left_list: Vec<i32>, mut right_list: Vec<i32>) -> i32 {
@@ -1,34 +1,25 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, parse_value, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let rows = parse_input(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Vec<i32>>, Error> {
+    parse_each_line(input, |line| line.split_whitespace().map(parse_value).collect())
+}
+
+pub fn part1(rows: &[Vec<i32>]) -> Result<Answer, Error> {
     let safe_count =
-        rows.into_iter()
+        rows.iter()
             .filter(|r| is_safe(r))
             .count();
-    println!("{}", safe_count);
+    Ok(Answer::from(safe_count))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let rows = parse_input(lines);
+pub fn part2(rows: &[Vec<i32>]) -> Result<Answer, Error> {
     let safe_count =
-        rows.into_iter()
+        rows.iter()
             .filter(|r| is_safe_with_dampener(r))
             .count();
-    println!("{}", safe_count);
-}
-
-fn parse_input(lines: Vec<String>) -> Vec<Vec<i32>> {
-    lines.into_iter()
-        .map(|line| {
-            line
-                .split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        })
-        .collect()
+    Ok(Answer::from(safe_count))
 }
 
 fn is_safe(sequence: &Vec<i32>) -> bool {
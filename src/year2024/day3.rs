@@ -1,38 +1,36 @@
-use crate::input_file::read_lines;
+use crate::input_file::{read_lines, PuzzleInput};
+use crate::captures::group;
+use crate::answer::Answer;
+use crate::error::Error;
 
 use regex::Regex;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let input = lines.concat();
+pub fn parse(input: &PuzzleInput) -> Result<String, Error> {
+    let lines = read_lines(input)?;
+    Ok(lines.concat())
+}
 
-    let total = sum_evaluated_muls(&input);
+pub fn part1(input: &str) -> Result<Answer, Error> {
+    let total = sum_evaluated_muls(input)?;
 
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let input = lines.concat();
-
+pub fn part2(input: &str) -> Result<Answer, Error> {
     // first remove all of the string that appears between "don't()" and "do()"
     let dont_do_regex = Regex::new(r"don't\(\).*?do\(\)").unwrap();
     let dont_to_end_regex = Regex::new(r"don't\(\).*$").unwrap();
-    let filtered_input = dont_do_regex.replace_all(&input, "");
+    let filtered_input = dont_do_regex.replace_all(input, "");
     let filtered_input = dont_to_end_regex.replace_all(&filtered_input, "");
 
-    let total = sum_evaluated_muls(&filtered_input);
+    let total = sum_evaluated_muls(&filtered_input)?;
     
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-fn sum_evaluated_muls(input: &str) -> i32 {
-    let mul_regex = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
-    mul_regex.captures_iter(&input)
-        .map(|caps| {
-            let lvalue: i32 = caps.get(1).unwrap().as_str().parse().unwrap();
-            let rvalue: i32 = caps.get(2).unwrap().as_str().parse().unwrap();
-            lvalue * rvalue
-        })
+fn sum_evaluated_muls(input: &str) -> Result<i32, Error> {
+    let mul_regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
+    mul_regex.captures_iter(input)
+        .map(|caps| Ok(group::<i32>(&caps, 1)? * group::<i32>(&caps, 2)?))
         .sum()
 }
@@ -1,8 +1,13 @@
-use crate::input_file::read_lines;
+use crate::input_file::{read_lines, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let lines: Vec<String> = lines.iter().map(|l| l.clone() + "|").collect();
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    let lines = read_lines(input)?;
+    Ok(lines.iter().map(|l| l.clone() + "|").collect())
+}
+
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
     let grid_width = lines[0].len() as i32;
     let input = lines.concat();
 
@@ -31,12 +36,10 @@ pub fn part1(input_file_path: &str) {
             })
             .sum();
     
-    println!("{}", no_of_xmases);
+    Ok(Answer::from(no_of_xmases))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let lines: Vec<String> = lines.iter().map(|l| l.clone() + "|").collect();
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
     let grid_width = lines[0].len() as i32;
     let input = lines.concat();
 
@@ -76,7 +79,7 @@ pub fn part2(input_file_path: &str) {
             })
             .sum();
 
-    println!("{}", no_of_x_mases);
+    Ok(Answer::from(no_of_x_mases))
 }
 
 fn west() -> i32 { -1 }
@@ -1,67 +1,60 @@
-use regex::{Captures, Regex};
+use lazy_static::lazy_static;
+use regex::Regex;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::captures::{named, named_str, parse_captures};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instruction_regex = Regex::new(r"(?P<dir>[LR])(?P<dist>\d+)").unwrap();
+pub fn parse(input: &PuzzleInput) -> Result<Vec<i32>, Error> {
+    parse_each_line(input, parse_rotation)
+}
+
+pub fn part1(rotations: &[i32]) -> Result<Answer, Error> {
     let mut dial = LoopCounter {
         max: 100,
         count: 50,
         zero_crosses: 0,
     };
-    let no_of_zeroes: i32 = lines
-        .into_iter()
-        .map(|line| match instruction_regex.captures(&line) {
-            Some(captures) => {
-                let dist = parse_i32_from_regex_capture(&captures, "dist");
-                match captures.name("dir").unwrap().as_str() {
-                    "L" => dial.add(dist * -1),
-                    "R" => dial.add(dist),
-                    _ => panic!("Invalid instruction"),
-                };
-                // println!("- dial {}", dial.value());
-                if dial.value() == 0 {
-                    1
-                } else {
-                    0
-                }
+    let no_of_zeroes: i32 = rotations
+        .iter()
+        .map(|rotation| {
+            dial.add(*rotation);
+            // println!("- dial {}", dial.value());
+            if dial.value() == 0 {
+                1
+            } else {
+                0
             }
-            None => panic!("Invalid instruction"),
         })
         .sum();
-    println!("{}", no_of_zeroes);
+    Ok(Answer::from(no_of_zeroes))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let instruction_regex = Regex::new(r"(?P<dir>[LR])(?P<dist>\d+)").unwrap();
+pub fn part2(rotations: &[i32]) -> Result<Answer, Error> {
     let mut dial = LoopCounter {
         max: 100,
         count: 50,
         zero_crosses: 0,
     };
-    for line in lines {
-        match instruction_regex.captures(&line) {
-            Some(captures) => {
-                let dist = parse_i32_from_regex_capture(&captures, "dist");
-                match captures.name("dir").unwrap().as_str() {
-                    "L" => dial.add(dist * -1),
-                    "R" => dial.add(dist),
-                    _ => panic!("Invalid instruction"),
-                };
-            }
-            None => panic!("Invalid instruction")
-        }
+    for rotation in rotations {
+        dial.add(*rotation);
     };
-    println!("{}", dial.zero_crosses());
+    Ok(Answer::from(dial.zero_crosses()))
 }
 
-fn parse_i32_from_regex_capture(captures: &Captures, capture_name: &str) -> i32 {
-    let value = captures.name(capture_name).unwrap().as_str();
-    value
-        .parse()
-        .expect(format!("Invalid {}: {}", capture_name, value).as_str())
+/// A rotation of the dial, negative to the left and positive to the right.
+fn parse_rotation(line: &str) -> Result<i32, Error> {
+    lazy_static! {
+        static ref INSTRUCTION_REGEX: Regex = Regex::new(r"(?P<dir>[LR])(?P<dist>\d+)").unwrap();
+    }
+    parse_captures(&INSTRUCTION_REGEX, line, |captures| {
+        let dist: i32 = named(captures, "dist")?;
+        match named_str(captures, "dir")? {
+            "L" => Ok(-dist),
+            _ => Ok(dist)
+        }
+    })
 }
 
 struct LoopCounter {
@@ -1,47 +1,47 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use num::pow;
 use regex::Regex;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, split_parse, PuzzleInput};
+use crate::answer::Answer;
+use crate::cancel;
+use crate::search::bfs;
+use crate::error::Error;
 
 const debugging: bool = false;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let machines =
-        lines.iter().map(|line| Machine::from_input_string(line)).collect_vec();
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Machine>, Error> {
+    parse_each_line(input, Machine::from_input_string)
+}
+
+pub fn part1(machines: &[Machine]) -> Result<Answer, Error> {
     let result: u64 =
         machines
             .iter()
-            .map(|machine| {
-                find_fewest_button_presses_lights(machine)
-            })
-            .sum();
-    println!("{}", result);
+            .map(find_fewest_button_presses_lights)
+            .sum::<Result<u64, Error>>()?;
+    Ok(Answer::from(result))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let machines =
-        lines.iter().map(|line| Machine::from_input_string(line)).collect_vec();
+pub fn part2(machines: &[Machine]) -> Result<Answer, Error> {
     let result: u64 =
         machines
             .iter()
             .map(|machine| {
-                let mut result_cache = HashMap::<String,u64>::new();
-                let answer = find_fewest_button_presses_jolts(machine, &mut result_cache, 0);
-                println!("{:?} {}", machine.joltages, answer);
-                answer
+                let mut result_cache = HashMap::<String,Option<u64>>::new();
+                let answer = find_fewest_button_presses_jolts(machine, &mut result_cache, 0)?
+                    .ok_or_else(|| Error::solve("No button presses reach the joltages."))?;
+                if debugging { println!("{:?} {}", machine.joltages, answer); }
+                Ok(answer)
             })
-            .sum();
-    println!("{}", result);
-
+            .sum::<Result<u64, Error>>()?;
+    Ok(Answer::from(result))
 }
 
 #[derive(Clone)]
-struct Machine {
+pub struct Machine {
     // represent indicator array and button wiring as binary
     // works for max array of 32 lights
     indicator_goal: u64,
@@ -52,7 +52,7 @@ struct Machine {
 
 impl Machine {
 
-    fn from_input_string(repr: &str) -> Machine {
+    fn from_input_string(repr: &str) -> Result<Machine, Error> {
         let ind_re = Regex::new(r"\[([.#]+)\]").unwrap();
         let but_re = Regex::new(r"\(((?:(?:\d+),?)+)\)").unwrap();
         let jol_re = Regex::new(r"\{((?:(?:\d+),?)+)\}").unwrap();
@@ -62,22 +62,30 @@ impl Machine {
                 let ind_str = caps.get(1).unwrap().as_str();
                 let bool_vec = ind_str.chars().map(|c| {
                     match c {
-                        '#' => true,
-                        '.' => false,
-                        x => panic!("bad char in indicator input '{}'", x)
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        x => Err(Error::parse(format!("bad char in indicator input '{}'", x)))
                     }
-                }).collect_vec();
+                }).collect::<Result<Vec<bool>, Error>>()?;
+                if bool_vec.len() > 32 {
+                    return Err(Error::parse("There can be at most 32 indicator lights."));
+                }
                 (bool_vec_to_num(&bool_vec), bool_vec.len() as u8)
             },
             None => {
-                panic!("failed to parse indicators: {}", repr);
+                return Err(Error::parse("failed to parse indicators"));
             }
         };
-        let buttons =
+        let button_indexes =
             but_re.captures_iter(repr)
-                .map(|button_caps| {
-                    let but_str = button_caps.get(1).unwrap().as_str();
-                    let indexes = parse_comma_delim_nums(but_str);
+                .map(|button_caps| split_parse(button_caps.get(1).unwrap().as_str(), ","))
+                .collect::<Result<Vec<Vec<u64>>, Error>>()?;
+        if let Some(i) = button_indexes.iter().flatten().find(|i| **i >= indicator_len as u64) {
+            return Err(Error::parse(format!("A button is wired to light {}, but there are only {}.", i, indicator_len)));
+        }
+        let buttons =
+            button_indexes.iter()
+                .map(|indexes| {
                     let mut bool_vec = Vec::<bool>::new();
                     for i in 0..indicator_len {
                         if indexes.contains(&(i as u64)) {
@@ -90,10 +98,8 @@ impl Machine {
                 })
                 .collect_vec();
         let buttons_nums =
-            but_re.captures_iter(repr)
-                .map(|button_caps| {
-                    let but_str = button_caps.get(1).unwrap().as_str();
-                    let indexes = parse_comma_delim_nums(but_str);
+            button_indexes.iter()
+                .map(|indexes| {
                     let mut num_vec = Vec::<u64>::new();
                     for i in 0..indicator_len {
                         if indexes.contains(&(i as u64)) {
@@ -107,63 +113,63 @@ impl Machine {
                 .collect_vec();
         let joltages = match jol_re.captures(repr) {
             Some(caps) => {
-                parse_comma_delim_nums(
-                    caps.get(1).unwrap().as_str())
+                split_parse(
+                    caps.get(1).unwrap().as_str(), ",")?
             },
             None => {
-                panic!("failed to parse indicators: {}", repr);
+                return Err(Error::parse("failed to parse joltages"));
             }
         };
-        Machine {
+        if joltages.len() != indicator_len as usize {
+            return Err(Error::parse(format!("Expected {} joltages, one per light, not {}.", indicator_len, joltages.len())));
+        }
+        Ok(Machine {
             indicator_goal,
             buttons,
             buttons_nums,
             joltages,
-        }
+        })
     }
 }
 
-fn find_fewest_button_presses_lights(machine: &Machine) -> u64 {
+fn find_fewest_button_presses_lights(machine: &Machine) -> Result<u64, Error> {
     // breadth-first search on tree of possible presses
-    let mut state_queue = VecDeque::<(u64, u64)>::new();
-    state_queue.push_back((0, 0));
-
-    // try all button presses, update machine state
-    loop {
-        let (current_ind_state, current_presses) = state_queue.pop_front()
-            .expect("reached end of bfs queue");
-        for button in machine.buttons.iter() {
+    let path = bfs(
+        0u64,
+        |current_ind_state| {
             // as indicators and buttons are represented as bit arrays,
             // XOR does the job of toggling on/off
-            let next_ind_state =
-                (current_ind_state ^ button, current_presses + 1);
-            if next_ind_state.0 == machine.indicator_goal {
-                return next_ind_state.1;
-            }
-            state_queue.push_back(next_ind_state);
-        }
-    }
+            let current_ind_state = *current_ind_state;
+            machine.buttons.iter().map(move |button| current_ind_state ^ button)
+        },
+        |ind_state| *ind_state == machine.indicator_goal
+    )?;
+    path.map(|path| path.cost as u64)
+        .ok_or_else(|| Error::solve("No buttons light up the indicators."))
 }
 
 fn find_fewest_button_presses_jolts(
-    machine: &Machine, result_cache: &mut HashMap<String,u64>, depth: usize
-) -> u64 {
+    machine: &Machine, result_cache: &mut HashMap<String,Option<u64>>, depth: usize
+) -> Result<Option<u64>, Error> {
+    // None means no pattern of presses reaches the joltages.
     // Had to look up a solution as could see bfs was intractable
     // https://www.reddit.com/r/adventofcode/comments/1pk87hl/2025_day_10_part_2_bifurcate_your_way_to_victory/
 
     let indent = " ".repeat(depth*2);
     //println!("{}call : {:?}", indent, machine.joltages);
     
+    cancel::check()?;
+
     // Look up result in cache
     let key = machine.joltages.iter().map(|n| n.to_string()).join(",");
     match result_cache.get(&key) {
-        Some(result) => return *result,
+        Some(result) => return Ok(*result),
         None => {}
     }
 
     // Base case - all joltage goals are 0, return 0
     if machine.joltages.iter().all(|j| *j == 0) {
-        return 0;
+        return Ok(Some(0));
     }
 
     // First find all patterns of single button presses
@@ -194,7 +200,7 @@ fn find_fewest_button_presses_jolts(
     // Because the remaining joltages are all even after application,
     // the joltages can be halved and the result from the recursion
     // can be multiplied by 2.
-    let mut min_presses = u64::max_value();
+    let mut min_presses = None;
     for (pattern, jolts_to_sub) in matching_patterns_and_new_goals {
         if debugging { println!("{}press: {:?} for {:?}", indent, pattern, jolts_to_sub); }
         let dbg = jolts_to_sub.clone();
@@ -206,32 +212,25 @@ fn find_fewest_button_presses_jolts(
             buttons_nums: machine.buttons_nums.clone(),
             joltages: new_goal
         };
-        let subres = find_fewest_button_presses_jolts(&next_machine, result_cache, depth + 1);
-        if subres == u64::max_value() {
-            if debugging { println!("{}impossible to reach target", indent)}
-            continue;
-        }
+        let subres = match find_fewest_button_presses_jolts(&next_machine, result_cache, depth + 1)? {
+            Some(subres) => subres,
+            None => {
+                if debugging { println!("{}impossible to reach target", indent)}
+                continue;
+            }
+        };
         let presses = pattern.iter().count() as u64 + (2 * subres);
         if debugging { println!("{}presses: {}", indent, presses); }
-        if presses < min_presses {
-            min_presses = presses;
+        if min_presses.is_none_or(|min| presses < min) {
+            min_presses = Some(presses);
         }
     }
     //println!("{}min presses {:?}: {}", indent, machine.joltages, min_presses);
     result_cache.insert(key, min_presses);
-    min_presses
+    Ok(min_presses)
     
 }
 
-fn parse_comma_delim_nums(repr: &str) -> Vec<u64> {
-    repr
-        .split(',')
-        .map(|s| {
-        s.parse().unwrap()
-    })
-        .collect()
-}
-
 fn bool_vec_to_num(bool_vec: &Vec<bool>) -> u64 {
     let mut sum = 0;
     for i in 0..bool_vec.len() {
@@ -1,9 +0,0 @@
-use crate::input_file::read_lines;
-
-pub fn part1(input_file_path: &str) {
-    let _lines = read_lines(input_file_path);
-}
-
-pub fn part2(input_file_path: &str) {
-    let _lines = read_lines(input_file_path);
-}
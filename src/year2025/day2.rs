@@ -1,12 +1,18 @@
 use std::collections::HashSet;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_value, read_lines, PuzzleInput};
 use crate::data_structs::NumRange;
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let ranges = parse_input(lines.first().unwrap()); // only one line
-    let max_of_ranges = get_max_of_ranges(&ranges);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<NumRange>, Error> {
+    let lines = read_lines(input)?;
+    let line = lines.first().ok_or_else(|| Error::parse("Input is empty."))?;
+    parse_input(line).map_err(|e| e.at_line(1, line)) // only one line
+}
+
+pub fn part1(ranges: &[NumRange]) -> Result<Answer, Error> {
+    let max_of_ranges = get_max_of_ranges(ranges);
     let mut total = 0;
     for i in 1..max_of_ranges {
         let id = make_repeated_num(i);
@@ -19,13 +25,11 @@ pub fn part1(input_file_path: &str) {
             total += id;
         }
     }
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let ranges = parse_input(lines.first().unwrap()); // only one line
-    let max_of_ranges = get_max_of_ranges(&ranges);
+pub fn part2(ranges: &[NumRange]) -> Result<Answer, Error> {
+    let max_of_ranges = get_max_of_ranges(ranges);
     let mut invalids = HashSet::<u64>::new();
     for i in 1..max_of_ranges {
         let ids = make_repeated_nums(i, max_of_ranges);
@@ -40,38 +44,39 @@ pub fn part2(input_file_path: &str) {
             }
         }
     }
-    println!("{}", invalids.iter().sum::<u64>());
+    Ok(Answer::from(invalids.iter().sum::<u64>()))
 }
 
-fn parse_input(line: &str) -> Vec<NumRange> {
+fn parse_input(line: &str) -> Result<Vec<NumRange>, Error> {
     line.split(',')
         .map(|str_range| {
             match str_range.split_once('-') {
-                Some((min, max)) => NumRange{
-                    min: min.parse().unwrap(),
-                    max: max.parse().unwrap()
-                },
-                None => panic!("Bad formatting in input")
+                Some((min, max)) => Ok(NumRange{
+                    min: parse_value(min)?,
+                    max: parse_value(max)?
+                }),
+                None => Err(Error::parse(format!("Bad formatting in range {}.", str_range)))
             }
         })
         .collect()
 }
 
-fn get_max_of_ranges(ranges: &Vec<NumRange>) -> u64 {
+fn get_max_of_ranges(ranges: &[NumRange]) -> u64 {
     ranges.iter().max_by(|x, y| x.max.cmp(&y.max)).unwrap().max
 }
 
 fn make_repeated_num(n: u64) -> u64 {
     let nstr = n.to_string();
     let repeated = format!("{}{}", nstr, nstr);
-    repeated.parse().unwrap()
+    // too big for a u64 is bigger than any range goes
+    repeated.parse().unwrap_or(u64::MAX)
 }
 
 fn make_repeated_nums(n: u64, max: u64) -> Vec<u64> {
     let mut nums = Vec::<u64>::new();
     let nstr = n.to_string();
     for i in 2..99 {
-        let repeated = nstr.repeat(i).parse().unwrap();
+        let repeated = nstr.repeat(i).parse().unwrap_or(u64::MAX);
         if repeated > max {
             break;
         }
@@ -1,15 +1,27 @@
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    parse_each_line(input, |line| {
+        if line.len() < 2 || !line.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::parse("Expected a bank of at least two batteries, as digits."));
+        }
+        Ok(line.to_string())
+    })
+}
+
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
     let result: u32 = lines.iter().map(|l| largest_joltage_2(l)).sum();
-    println!("{}", result);
+    Ok(Answer::from(result))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
+    if lines.iter().any(|l| l.len() < 12) {
+        return Err(Error::solve("Every bank needs at least twelve batteries."));
+    }
     let result: u64 = lines.iter().map(|l| largest_joltage_12(l)).sum();
-    println!("{}", result);
+    Ok(Answer::from(result))
 }
 
 fn largest_joltage_2(battery_bank: &str) -> u32 {
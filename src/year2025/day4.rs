@@ -1,15 +1,24 @@
-use crate::{data_structs::Grid, input_file::read_lines};
+use crate::{data_structs::Grid, input_file::{read_lines, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_input(lines);
-    let result = accessible_rolls(&grid);
-    println!("{}", result);
+pub fn parse(input: &PuzzleInput) -> Result<Grid<bool>, Error> {
+    Grid::from_lines(&read_lines(input)?, |c| {
+        match c {
+            '@' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(Error::parse(format!("invalid char {} in input", c)))
+        }
+    })
+}
+
+pub fn part1(grid: &Grid<bool>) -> Result<Answer, Error> {
+    let result = accessible_rolls(grid);
+    Ok(Answer::from(result))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let mut grid = parse_input(lines);
+pub fn part2(grid: &Grid<bool>) -> Result<Answer, Error> {
+    let mut grid = grid.clone();
     let mut total = 0;
     loop {
         let (new_grid, num_removed) = remove_accessible_rolls(&grid);
@@ -19,55 +28,14 @@ pub fn part2(input_file_path: &str) {
         total += num_removed;
         grid = new_grid;
     }
-    println!("{}", total);
-}
-
-fn parse_input(lines: Vec<String>) -> Grid<bool> {
-    let elements: Vec<bool> =
-        lines.iter().map(|line| {
-            line.chars().map(|c| {
-                match c {
-                    '@' => true,
-                    '.' => false,
-                    _ => panic!("invalid char {} in input", c)
-                }
-            })
-        }).flatten().collect();
-    
-    Grid::<bool>::with_elements(
-        lines[0].len(),
-        lines.len(),
-        elements
-    )
+    Ok(Answer::from(total))
 }
 
 fn accessible_rolls(grid: &Grid<bool>) -> u32 {
     let mut total = 0u32;
-    for x in 0isize..grid.xsize as isize {
-        for y in 0isize..grid.ysize as isize {
-            if !*grid.get(x as usize, y as usize) {
-                continue;
-            }
-            let mut neighbours = 0;
-            for xoff in -1isize..2isize {
-                let curr_x = x as isize + xoff;
-                if curr_x < 0 || curr_x >= grid.xsize as isize {
-                    continue;
-                }
-                for yoff in -1..2 {
-                    if xoff == 0 && yoff == 0 {
-                        continue;
-                    }
-                    let curr_y = y + yoff;
-                    if curr_y < 0 || curr_y >= grid.ysize as isize {
-                        continue;
-                    }
-                    if *grid.get(curr_x as usize, curr_y as usize) {
-                        neighbours += 1;
-                    }
-                }
-            }
-            if neighbours < 4 {
+    for x in 0..grid.xsize {
+        for y in 0..grid.ysize {
+            if is_accessible(grid, x, y) {
                 total += 1;
             }
         }
@@ -78,35 +46,18 @@ fn accessible_rolls(grid: &Grid<bool>) -> u32 {
 fn remove_accessible_rolls(grid: &Grid<bool>) -> (Grid<bool>, u32) {
     let mut new_grid = grid.clone();
     let mut total = 0u32;
-    for x in 0isize..grid.xsize as isize {
-        for y in 0isize..grid.ysize as isize {
-            if !*grid.get(x as usize, y as usize) {
-                continue;
-            }
-            let mut neighbours = 0;
-            for xoff in -1isize..2isize {
-                let curr_x = x as isize + xoff;
-                if curr_x < 0 || curr_x >= grid.xsize as isize {
-                    continue;
-                }
-                for yoff in -1..2 {
-                    if xoff == 0 && yoff == 0 {
-                        continue;
-                    }
-                    let curr_y = y + yoff;
-                    if curr_y < 0 || curr_y >= grid.ysize as isize {
-                        continue;
-                    }
-                    if *grid.get(curr_x as usize, curr_y as usize) {
-                        neighbours += 1;
-                    }
-                }
-            }
-            if neighbours < 4 {
+    for x in 0..grid.xsize {
+        for y in 0..grid.ysize {
+            if is_accessible(grid, x, y) {
                 total += 1;
-                new_grid.set(x as usize, y as usize, false);
+                new_grid.set(x, y, false);
             }
         }
     }
     (new_grid, total)
 }
+
+/// Whether there is a roll at x,y with fewer than four rolls around it.
+fn is_accessible(grid: &Grid<bool>, x: usize, y: usize) -> bool {
+    *grid.get(x, y) && grid.neighbours8(x, y).filter(|(_, roll)| **roll).count() < 4
+}
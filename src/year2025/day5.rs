@@ -1,22 +1,29 @@
 use std::collections::HashSet;
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each, parse_two_sections, parse_value, PuzzleInput};
 use crate::data_structs::NumRange;
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (ranges, nums) = parse_input(lines);
+pub fn parse(input: &PuzzleInput) -> Result<(Vec<NumRange>, Vec<u64>), Error> {
+    parse_two_sections(
+        input,
+        |lines| parse_each(lines, parse_range),
+        |lines| parse_each(lines, parse_value)
+    )
+}
+
+pub fn part1((ranges, nums): &(Vec<NumRange>, Vec<u64>)) -> Result<Answer, Error> {
     let total = nums.iter().filter(|num| {
         ranges.iter().filter(|r| {
             r.includes(**num)
         }).count() > 0
     }).count();
-    println!("{}", total);
+    Ok(Answer::from(total))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let (mut ranges, _) = parse_input(lines);
+pub fn part2((ranges, _): &(Vec<NumRange>, Vec<u64>)) -> Result<Answer, Error> {
+    let mut ranges = ranges.clone();
     ranges.sort_by(|r,s| {r.min.cmp(&s.min)});
     let mut combined_ranges = Vec::<NumRange>::new();
     let mut deleted_range_ids = HashSet::<usize>::new();
@@ -44,32 +51,16 @@ pub fn part2(input_file_path: &str) {
         combined_ranges.iter().map(|r| {
             r.member_count()
         }).sum();
-    println!("{}", id_count);
+    Ok(Answer::from(id_count))
 }
 
-fn parse_input(lines: Vec<String>) -> (Vec<NumRange>, Vec<u64>) {
-    let mut range_mode = true;
-    let mut ranges = Vec::<NumRange>::new();
-    let mut nums = Vec::<u64>::new();
-    for line in lines {
-        if line.is_empty() {
-            range_mode = false;
-            continue;
-        }
-        if range_mode {
-            ranges.push(
-                match line.split_once('-') {
-                    Some((min, max)) => NumRange{
-                        min: min.parse().unwrap(),
-                        max: max.parse().unwrap()
-                    },
-                    None => panic!("Bad formatting in input ranges")
-                }
-            );
-        } else {
-            nums.push(line.parse().unwrap());
-        }
+fn parse_range(line: &str) -> Result<NumRange, Error> {
+    match line.split_once('-') {
+        Some((min, max)) => Ok(NumRange{
+            min: parse_value(min)?,
+            max: parse_value(max)?
+        }),
+        None => Err(Error::parse("Expected a range MIN-MAX."))
     }
-    (ranges, nums)
 }
 
@@ -1,14 +1,36 @@
-use crate::{data_structs::Grid, input_file::read_lines};
+use crate::{data_structs::Grid, input_file::{parse_value, read_lines, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_input_human(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<String>, Error> {
+    let lines = read_lines(input)?;
+    if lines.len() < 2 {
+        return Err(Error::parse("Expected rows of numbers above a row of operators."));
+    }
+    let problem_count = lines[lines.len() - 1].split_whitespace().count();
+    for (i, line) in lines.iter().enumerate() {
+        let allowed = if i == lines.len() - 1 { "*+ " } else { "0123456789 " };
+        if let Some(c) = line.chars().find(|c| !allowed.contains(*c)) {
+            return Err(Error::parse(format!("Unexpected {} in the worksheet.", c)).at_line(i + 1, line));
+        }
+        if line.len() != lines[0].len() {
+            return Err(Error::parse("Every line must be the same width.").at_line(i + 1, line));
+        }
+        if line.split_whitespace().count() != problem_count {
+            return Err(Error::parse("Every line must have one entry per problem.").at_line(i + 1, line));
+        }
+    }
+    Ok(lines)
+}
+
+pub fn part1(lines: &[String]) -> Result<Answer, Error> {
+    let grid = parse_input_human(lines.to_vec());
     let result: u64 =
-        grid_to_human_problems(&grid)
+        grid_to_human_problems(&grid)?
             .iter()
             .map(|p| p.solve())
             .sum();
-    println!("{}", result);
+    Ok(Answer::from(result))
 }
 
 fn parse_input_human(lines: Vec<String>) -> Grid<String> {
@@ -29,31 +51,30 @@ fn parse_input_human(lines: Vec<String>) -> Grid<String> {
         elements)
 }
 
-fn grid_to_human_problems(grid: &Grid<String>) -> Vec<Problem> {
+fn grid_to_human_problems(grid: &Grid<String>) -> Result<Vec<Problem>, Error> {
     // create problem from each column
     (0..grid.xsize).map(|x| {
-        Problem {
+        Ok(Problem {
             operands: (0..grid.ysize-1).map(|y| {
-                grid.get(x,y).parse().unwrap()
-            }).collect(),
+                parse_value(grid.get(x,y))
+            }).collect::<Result<_, _>>()?,
             operator: match grid.get(x,grid.ysize-1).as_str() {
                 "*" => Operator::Product,
                 "+" => Operator::Sum,
-                _ => panic!("Invalid operator")
+                op => return Err(Error::solve(format!("Invalid operator {}", op)))
             }
-        }
+        })
     }).collect()
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_input_cephalopod(lines);
+pub fn part2(lines: &[String]) -> Result<Answer, Error> {
+    let grid = parse_input_cephalopod(lines.to_vec());
     let result: u64 =
-        grid_to_cephalopod_problems(&grid)
+        grid_to_cephalopod_problems(&grid)?
             .iter()
             .map(|p| p.solve())
             .sum();
-    println!("{}", result);
+    Ok(Answer::from(result))
 }
 
 fn parse_input_cephalopod(lines: Vec<String>) -> Grid<char> {
@@ -72,23 +93,21 @@ fn parse_input_cephalopod(lines: Vec<String>) -> Grid<char> {
         elements)
 }
 
-fn grid_to_cephalopod_problems(grid: &Grid<char>) -> Vec<Problem> {
+fn grid_to_cephalopod_problems(grid: &Grid<char>) -> Result<Vec<Problem>, Error> {
     let mut problems = Vec::<Problem>::new();
     let mut operands =  Vec::<u64>::new();
     let mut operator = Operator::Sum;
-    for x in 0..grid.xsize {
-        // convert grid of chars to strings for each column
-        let num_str: String =
-            (0..grid.ysize-1).map(|y| {
-                grid.get(x,y)
-            }).collect::<String>().trim().to_string();
+    // read the problems column by column
+    for column in grid.transpose().rows() {
+        let (digits, operator_char) = column.split_at(column.len() - 1);
+        let num_str: String = digits.iter().collect::<String>().trim().to_string();
         if !num_str.is_empty() {
-            operands.push(num_str.trim().parse().unwrap());
-            match grid.get(x,grid.ysize-1) {
+            operands.push(parse_value(&num_str)?);
+            match operator_char[0] {
                 '*' => operator = Operator::Product,
                 '+' => operator = Operator::Sum,
                 ' ' => {},
-                c => panic!("Invalid char in operator spot {}", c)
+                c => return Err(Error::solve(format!("Invalid char in operator spot {}", c)))
             };
         } else {
             //println!("{:?} {:?}", operator, &operands);
@@ -105,7 +124,7 @@ fn grid_to_cephalopod_problems(grid: &Grid<char>) -> Vec<Problem> {
             operands,
             operator: operator.clone()
         });
-    problems
+    Ok(problems)
 }
 
 #[derive(Clone, Debug)]
@@ -1,53 +1,56 @@
 use std::collections::HashMap;
 
-use crate::{data_structs::{Grid}, input_file::read_lines};
+use crate::{data_structs::{Grid}, input_file::{read_lines, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let grid = parse_input(lines);
-    let mut simulation = TachyonSimulation::new(grid);
+pub fn parse(input: &PuzzleInput) -> Result<(usize, Grid<TachyonCell>), Error> {
+    let lines = read_lines(input)?;
+    let first_line = lines.first().ok_or_else(|| Error::parse("Input is empty."))?;
+    let startx = find_start(first_line).map_err(|e| e.at_line(1, first_line))?;
+    if let Some(i) = lines.iter().skip(1).position(|line| line.contains('S')) {
+        return Err(Error::parse("The start must be on the first line.").at_line(i + 2, &lines[i + 1]));
+    }
+    Ok((startx, parse_input(&lines)?))
+}
+
+pub fn part1((_, grid): &(usize, Grid<TachyonCell>)) -> Result<Answer, Error> {
+    let mut simulation = TachyonSimulation::new(grid.clone());
     simulation.simulate();
-    println!("{}", simulation.split_count);
+    Ok(Answer::from(simulation.split_count))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let startx = find_start(lines.first().unwrap());
-    let grid = parse_input(lines);
-    let mut simulation = TachyonSimulation::new(grid);
+pub fn part2(&(startx, ref grid): &(usize, Grid<TachyonCell>)) -> Result<Answer, Error> {
+    let mut simulation = TachyonSimulation::new(grid.clone());
     let timelines = simulation.simulate_tachyon(startx, 0);
-    println!("{}", timelines);
+    Ok(Answer::from(timelines))
 }
 
-fn find_start(first_line: &str) -> usize {
-    first_line.char_indices().find(|(_, c)| *c == 'S').unwrap().0
+fn find_start(first_line: &str) -> Result<usize, Error> {
+    // splitters need a row above them for beams to come from
+    if first_line.contains('^') {
+        return Err(Error::parse("There can't be splitters on the first line."));
+    }
+    match first_line.match_indices('S').map(|(x, _)| x).collect::<Vec<_>>()[..] {
+        [startx] => Ok(startx),
+        _ => Err(Error::parse("Expected one start S on the first line."))
+    }
 }
 
-fn parse_input(lines: Vec<String>) -> Grid<TachyonCell> {
+fn parse_input(lines: &[String]) -> Result<Grid<TachyonCell>, Error> {
     // parse all chars into a grid
-    Grid::<TachyonCell>::with_elements(
-        lines[0].len(),
-        lines.len(),
-        lines
-            .iter()
-            .map(|line| {
-                line
-                    .chars()
-                    .map(|c| {
-                        match c {
-                            'S' => TachyonCell::Start,
-                            '.' => TachyonCell::Empty,
-                            '^' => TachyonCell::Splitter,
-                            x => panic!("Invalid character in input {}", x)
-                        }
-                    })
-            })
-            .flatten()
-            .collect())
+    Grid::from_lines(lines, |c| {
+        match c {
+            'S' => Ok(TachyonCell::Start),
+            '.' => Ok(TachyonCell::Empty),
+            '^' => Ok(TachyonCell::Splitter),
+            x => Err(Error::parse(format!("Invalid character in input {}", x)))
+        }
+    })
 }
 
 #[derive(Clone)]
-enum TachyonCell {
+pub enum TachyonCell {
     Start,
     Empty,
     Beam,
@@ -2,12 +2,15 @@ use std::collections::HashSet;
 use itertools::Itertools;
 use num::{integer::sqrt, pow};
 
-use crate::input_file::read_lines;
+use crate::input_file::{parse_each_line, split_parse, PuzzleInput};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let coords = parse_input(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Coord3>, Error> {
+    parse_each_line(input, parse_coord)
+}
 
+pub fn part1(coords: &[Coord3]) -> Result<Answer, Error> {
     let mut pairs = Vec::<(Coord3, Coord3)>::new();
     for (i, c1) in coords.iter().enumerate() {
         for (_, c2) in coords.iter().enumerate().skip_while(|(k,_)| k <= &i) {
@@ -33,16 +36,13 @@ pub fn part1(input_file_path: &str) {
         // get the biggest three circuits
         .take(3)
         // multiply the sizes
-        .map(|c| {println!("{}", c.len()); c.len() as u64})
+        .map(|c| c.len() as u64)
         .product();
 
-    println!("{}", result);
+    Ok(Answer::from(result))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let coords = parse_input(lines);
-
+pub fn part2(coords: &[Coord3]) -> Result<Answer, Error> {
     let mut pairs = Vec::<(Coord3, Coord3)>::new();
     for (i, c1) in coords.iter().enumerate() {
         for (_, c2) in coords.iter().enumerate().skip_while(|(k,_)| k <= &i) {
@@ -59,28 +59,21 @@ pub fn part2(input_file_path: &str) {
         circuits = add_connection_to_circuit(&circuits, &cxn.0, &cxn.1);
         // if everything is connected in one big circuit, return x coords multiplied
         if circuits.len() == 1 && circuits.first().unwrap().len() == coords.len() {
-            println!("{}", cxn.0.x * cxn.1.x);
-            break;
+            return Ok(Answer::from(cxn.0.x * cxn.1.x));
         }
     }
+    Err(Error::solve("Junction boxes never formed a single circuit."))
 }
 
-fn parse_input(lines: Vec<String>) -> Vec<Coord3> {
-    lines
-        .iter()
-        .map(|line| {
-            let mut parts = line.splitn(3, ",");
-            Coord3 {
-                x: parts.next().unwrap().parse().unwrap(),
-                y: parts.next().unwrap().parse().unwrap(),
-                z: parts.next().unwrap().parse().unwrap()
-            }
-        })
-        .collect()
+fn parse_coord(line: &str) -> Result<Coord3, Error> {
+    match split_parse(line, ",")?[..] {
+        [x, y, z] => Ok(Coord3 { x, y, z }),
+        _ => Err(Error::parse("Expected a junction box at x,y,z."))
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
-struct Coord3 {
+pub struct Coord3 {
     x: i64,
     y: i64,
     z: i64
@@ -2,12 +2,19 @@ use std::{cmp::{max, min}};
 
 use itertools::Itertools;
 
-use crate::{data_structs::{Coord, manhattan_dist}, input_file::read_lines};
+use crate::{data_structs::{Coord, manhattan_dist}, input_file::{parse_each_line, split_parse, PuzzleInput}};
+use crate::answer::Answer;
+use crate::error::Error;
 
-pub fn part1(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let coords = parse_input(lines);
+pub fn parse(input: &PuzzleInput) -> Result<Vec<Coord>, Error> {
+    let coords = parse_each_line(input, parse_coord)?;
+    if coords.len() < 2 {
+        return Err(Error::parse("Expected at least two red tiles."));
+    }
+    Ok(coords)
+}
 
+pub fn part1(coords: &[Coord]) -> Result<Answer, Error> {
     // get all pairs of coords and find the largest manhattan distance
     // this will equate to the largest area if a rectangle is made
     let mut pairs = Vec::<(Coord, Coord)>::new();
@@ -23,18 +30,14 @@ pub fn part1(input_file_path: &str) {
                 manhattan_dist(&b1, &b2).cmp(&manhattan_dist(&a1, &a2))
             })
             .collect_vec();
-    let largest_corners = sorted_by_largest_manhattan.first().unwrap();
-    println!("{:?}", largest_corners);
+    let largest_corners = sorted_by_largest_manhattan.first().expect("There are at least two tiles.");
     let largest_area =
         (max(largest_corners.0.x, largest_corners.1.x) - min(largest_corners.0.x, largest_corners.1.x) + 1) *
             (max(largest_corners.0.y, largest_corners.1.y) - min(largest_corners.0.y, largest_corners.1.y) + 1);
-    println!("{}", largest_area);
+    Ok(Answer::from(largest_area))
 }
 
-pub fn part2(input_file_path: &str) {
-    let lines = read_lines(input_file_path);
-    let coords = parse_input(lines);
-
+pub fn part2(coords: &[Coord]) -> Result<Answer, Error> {
     // get all pairs of coords and find the largest manhattan distance
     // this will equate to the largest area if a rectangle is made
     let mut pairs = Vec::<(Coord, Coord)>::new();
@@ -70,14 +73,13 @@ pub fn part2(input_file_path: &str) {
         let intersect = outline_points.iter().any(|p| point_within_rect(p, rect_corners));
         if !intersect {
             // no intersection means this is our largest rect
-            println!("{:?}", rect_corners);
             let largest_area =
                 (max(rect_corners.0.x, rect_corners.1.x) - min(rect_corners.0.x, rect_corners.1.x) + 1) *
                     (max(rect_corners.0.y, rect_corners.1.y) - min(rect_corners.0.y, rect_corners.1.y) + 1);
-            println!("{}", largest_area);
-            break;
+            return Ok(Answer::from(largest_area));
         }
     }
+    Err(Error::solve("No rectangle fits inside the outline."))
 }
 
 fn area_of_rect(rect_corners: &(Coord, Coord)) -> usize {
@@ -85,19 +87,11 @@ fn area_of_rect(rect_corners: &(Coord, Coord)) -> usize {
         (max(rect_corners.0.y, rect_corners.1.y) - min(rect_corners.0.y, rect_corners.1.y) + 1)
 }
 
-fn parse_input(lines: Vec<String>) -> Vec<Coord> {
-    lines
-        .iter()
-        .map(|line| {
-            match line.split_once(',') {
-                Some((x, y)) => Coord {
-                        x: x.parse().unwrap(),
-                        y: y.parse().unwrap()
-                    },
-                None => panic!("Bad formatting in input")
-            }
-        })
-        .collect()
+fn parse_coord(line: &str) -> Result<Coord, Error> {
+    match split_parse(line, ",")?[..] {
+        [x, y] => Ok(Coord { x, y }),
+        _ => Err(Error::parse("Expected a red tile at x,y."))
+    }
 }
 
 fn point_within_rect(point: &Coord, rect_corners: &(Coord, Coord)) -> bool {
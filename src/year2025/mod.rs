@@ -1,39 +1,7 @@
-mod day1; mod day2; mod day3; mod day4;  mod day5;  mod day6;
-mod day7; mod day8; mod day9; mod day10; mod day11; mod day12;
-
-/// Dispatch the correct function given the day and part
-/// given.
-pub fn solve(day: u8, part: u8, input_file_path: &str) {
-    match (day, part) {
-        (1, 1) => day1::part1(input_file_path),
-        (1, 2) => day1::part2(input_file_path),
-        (2, 1) => day2::part1(input_file_path),
-        (2, 2) => day2::part2(input_file_path),
-        (3, 1) => day3::part1(input_file_path),
-        (3, 2) => day3::part2(input_file_path),
-        (4, 1) => day4::part1(input_file_path),
-        (4, 2) => day4::part2(input_file_path),
-        (5, 1) => day5::part1(input_file_path),
-        (5, 2) => day5::part2(input_file_path),
-        (6, 1) => day6::part1(input_file_path),
-        (6, 2) => day6::part2(input_file_path),
-        (7, 1) => day7::part1(input_file_path),
-        (7, 2) => day7::part2(input_file_path),
-        (8, 1) => day8::part1(input_file_path),
-        (8, 2) => day8::part2(input_file_path),
-        (9, 1) => day9::part1(input_file_path),
-        (9, 2) => day9::part2(input_file_path),
-        (10, 1) => day10::part1(input_file_path),
-        (10, 2) => day10::part2(input_file_path),
-        (11, 1) => day11::part1(input_file_path),
-        (11, 2) => day11::part2(input_file_path),
-        (12, 1) => day12::part1(input_file_path),
-        (12, 2) => day12::part2(input_file_path),
-        _ => {
-            eprintln!(
-                "Solution to 2025 day {} part {} not yet implemented.",
-                day, part
-            );
-        }
-    }
-}
+crate::solution::register_days!(
+    year: 2025,
+    days: [
+        day1, day2, day3, day4, day5, day6,
+        day7, day8, day9, day10,
+    ],
+);